@@ -1,9 +1,9 @@
 use dataframe::{Error, macros::DataFrame};
 
 #[derive(DataFrame)]
-struct MyData {
-    nationality: String,
-    name: String,
+struct MyData<'a> {
+    nationality: &'a str,
+    name: &'a str,
     xg: f64,
     goals: usize,
 }
@@ -12,5 +12,11 @@ fn main() -> Result<(), Error> {
     let df = MyData::read_csv("examples/data.csv")?;
     println!("{:?}", df);
 
+    let input = std::fs::read_to_string("examples/data.csv")?;
+    let records = MyData::read_records(&input)?;
+    records.iter().for_each(|record| {
+        println!("{} ({}): {} goals from {} xg", record.name, record.nationality, record.goals, record.xg);
+    });
+
     Ok(())
 }
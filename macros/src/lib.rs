@@ -1,13 +1,25 @@
-use proc_macro;
-
 #[derive(Debug)]
 enum ParseError {
-    IdentNotFound
+    IdentNotFound,
+    FieldsNotFound,
+    UnknownAttribute(String),
+    PrefixWithoutFlatten(String),
+    EnumNotFound,
+    NonUnitVariant(String),
+    ExpectedString(String),
 }
 
 impl std::fmt::Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self)
+        match self {
+            Self::IdentNotFound => f.write_str("DataFrame can only be derived on a named struct"),
+            Self::FieldsNotFound => f.write_str("DataFrame can only be derived on a struct with named fields"),
+            Self::UnknownAttribute(attr) => write!(f, "Unknown dataframe attribute `{attr}`"),
+            Self::PrefixWithoutFlatten(field) => write!(f, "`prefix` on field `{field}` requires `flatten`"),
+            Self::EnumNotFound => f.write_str("DataFrameEnum can only be derived on a non-generic enum"),
+            Self::NonUnitVariant(variant) => write!(f, "DataFrameEnum requires unit variants, `{variant}` carries data"),
+            Self::ExpectedString(lit) => write!(f, "Expected a string literal, found `{lit}`"),
+        }
     }
 }
 
@@ -37,7 +49,10 @@ struct ParsedTokenStream {
     visibility: Visibility,
     name: proc_macro::Ident,
     generics: Option<Vec<proc_macro::TokenTree>>,
+    where_clause: Option<Vec<proc_macro::TokenTree>>,
     data: Option<Vec<Vec<proc_macro::TokenTree>>>,
+    strict: bool,
+    is_enum: bool,
 }
 
 impl ParsedTokenStream {
@@ -45,137 +60,521 @@ impl ParsedTokenStream {
         self.name.clone()
     }
 
+    fn params(&self) -> Vec<Vec<proc_macro::TokenTree>> {
+        self.generics
+            .as_ref()
+            .map(|generics| split_top_level(generics))
+            .unwrap_or_default()
+    }
+
+    // generic parameters as declared, minus any defaults: `'a, T: FromStr`
     fn generics(&self) -> Option<String> {
-        if let Some(ref generics) = self.generics {
-            Some(generics.iter().map(|tree| tree.to_string()).collect::<String>())
-        } else { None }
+        self.generics.as_ref()?;
+        Some(
+            self.params()
+                .iter()
+                .map(|param| {
+                    let default = position_top_level(param, '=').unwrap_or(param.len());
+                    to_string(&param[..default])
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    // generic arguments applied to the struct: `'a, T`
+    fn generic_args(&self) -> Option<String> {
+        self.generics.as_ref()?;
+        Some(
+            self.params()
+                .iter()
+                .map(|param| match &param[..] {
+                    [proc_macro::TokenTree::Punct(_), lifetime, ..] => format!("'{lifetime}"),
+                    [proc_macro::TokenTree::Ident(c), name, ..] if c.to_string() == "const" => name.to_string(),
+                    [name, ..] => name.to_string(),
+                    [] => String::new(),
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
     }
 
     fn lifetime(&self) -> Option<Vec<proc_macro::Ident>> {
-        if let Some(ref generics) = self.generics {
-            generics
+        self.generics.as_ref()?;
+        Some(
+            self.params()
                 .iter()
-                .map(|t| match t {
-                    proc_macro::TokenTree::Ident(ident) => Some(ident.clone()),
+                .filter_map(|param| match &param[..] {
+                    [proc_macro::TokenTree::Punct(p), proc_macro::TokenTree::Ident(lifetime), ..] if p.as_char() == '\'' => {
+                        Some(lifetime.clone())
+                    },
                     _ => None
                 })
-                .collect::<Option<Vec<_>>>()
-        } else { None }
+                .collect()
+        )
+    }
+
+    fn type_params(&self) -> Vec<String> {
+        self.params()
+            .iter()
+            .filter_map(|param| match &param[..] {
+                [proc_macro::TokenTree::Ident(name), ..] if name.to_string() != "const" => Some(name.to_string()),
+                _ => None
+            })
+            .collect()
+    }
+
+    fn fields(&self) -> Result<Vec<FieldDef>, ParseError> {
+        if self.is_enum {
+            return Err(ParseError::IdentNotFound);
+        }
+        let data = self.data.as_ref().ok_or(ParseError::FieldsNotFound)?;
+        data.iter().map(|field| FieldDef::parse(field)).collect()
     }
 
-    fn fnames(&self) -> Option<Vec<String>> {
-        if let Some(ref data) = self.data {
-            Some(data.iter().map(|tree| tree[0].to_string()).collect())
-        } else { None }
+    // bounds every type parameter to `Field<'src>`, plus `'src` outliving
+    // every lifetime parameter when `src` is borrowed
+    fn field_bounds(&self, src: &str, borrowed: bool) -> Vec<String> {
+        let lifetimes = self
+            .lifetime()
+            .unwrap_or_default()
+            .iter()
+            .filter(|_| borrowed)
+            .map(|lifetime| format!("{src}: '{lifetime}"))
+            .collect::<Vec<_>>();
+        let types = self
+            .type_params()
+            .iter()
+            .map(|ty| format!("{ty}: ::dataframe::Field<{src}>"))
+            .collect::<Vec<_>>();
+        [lifetimes, types].concat()
     }
 
-    fn ftypes(&self) -> Option<Vec<String>> {
-        if let Some(ref data) = self.data {
-            Some(data.iter().map(|v| v[2..].iter().map(ToString::to_string).collect::<String>()).collect())
-        } else { None }
+    // the struct's own where clause, extended with `bounds`
+    fn impl_where(&self, bounds: &[String]) -> String {
+        let mut clause = self.where_clause.as_deref().map(to_string).unwrap_or_default();
+        if bounds.is_empty() {
+            return clause;
+        }
+        if clause.is_empty() {
+            clause.push_str("where ");
+        } else if !clause.trim_end().ends_with(',') {
+            clause.push_str(", ");
+        }
+        clause + &bounds.join(", ")
     }
 
-    // FIXME: better deserialization
-    fn into_token_stream(&self) -> proc_macro::TokenStream {
+    fn to_token_stream(&self) -> Result<proc_macro::TokenStream, ParseError> {
         let visibility = self.visibility.to_str();
         let name = self.name();
-        let _generics = self.generics();
-        let _lifetime = self.lifetime();
-        let fnames = self.fnames().unwrap();
-        let ftypes = self.ftypes().unwrap();
+        let generics = self.generics().unwrap_or_default();
+        let generic_args = self.generic_args().map(|g| format!("<{g}>")).unwrap_or_default();
+        let fields = self.fields()?;
+        let strict = self.strict;
 
-        format!("
-            use dataframe::{{DataFrame, Val}};
+        let where_clause = self.impl_where(&[]);
+        let flatten_bounds = |src: &'static str| {
+            fields
+                .iter()
+                .filter(|field| field.flatten.is_some())
+                .map(move |field| format!("{}: ::dataframe::Record<{src}>", field.ty))
+        };
+        let schema_where = self.impl_where(&self.field_bounds("'static", false).into_iter().chain(flatten_bounds("'static")).collect::<Vec<_>>());
+        let record_where = self.impl_where(&self.field_bounds("'__src", true).into_iter().chain(flatten_bounds("'__src")).collect::<Vec<_>>());
 
-            impl {name} {{
-                {visibility} fn read_csv<P: AsRef<std::path::Path>>(path: P) -> Result<DataFrame, Error> {{
-                    use std::io::Read;
+        // flattened fields contribute their own schema under their prefix
+        let schema = fields
+            .iter()
+            .map(|FieldDef { name, ty, flatten }| match flatten {
+                Some(prefix) => format!("schema.flatten({prefix:?}, <{ty} as ::dataframe::Record<'__src>>::SCHEMA);"),
+                None => format!("schema.column(\"\", {name:?}, <{ty} as ::dataframe::Field<'__src>>::DTYPE);"),
+            })
+            .collect::<String>();
+        let parse_row = fields
+            .iter()
+            .map(|FieldDef { name, ty, flatten }| match flatten {
+                Some(_) => format!("{name}: <{ty} as ::dataframe::Record<'__src>>::parse_row(row, positions)?,"),
+                None => format!("{name}: <{ty} as ::dataframe::Field<'__src>>::parse_field(::std::clone::Clone::clone(::dataframe::__private::cell(row, positions)?))?,"),
+            })
+            .collect::<String>();
+        let parse_vals = fields
+            .iter()
+            .map(|FieldDef { ty, flatten, .. }| match flatten {
+                Some(_) => format!("<{ty} as ::dataframe::Record<'__src>>::parse_vals(row, positions, data)?;"),
+                None => format!("data.push(<{ty} as ::dataframe::Field<'__src>>::parse_val(::dataframe::__private::cell(row, positions)?)?);"),
+            })
+            .collect::<String>();
+        let to_vals = fields
+            .iter()
+            .map(|FieldDef { name, ty, flatten }| match flatten {
+                Some(_) => format!("<{ty} as ::dataframe::Record<'__src>>::to_vals(&self.{name}, data);"),
+                None => format!("data.push(<{ty} as ::dataframe::Field<'__src>>::to_val(&self.{name}));"),
+            })
+            .collect::<String>();
+
+        let ts = format!("
+            impl<{generics}> {name}{generic_args} {schema_where} {{
+                {visibility} const SCHEMA: &'static [(&'static str, ::dataframe::DType)] = <Self as ::dataframe::Record<'static>>::SCHEMA;
+            }}
+
+            impl<'__src, {generics}> ::dataframe::Record<'__src> for {name}{generic_args} {record_where} {{
+                const SCHEMA: &'static [(&'static str, ::dataframe::DType)] =
+                    (&const {{ (&const {{ let mut schema = ::dataframe::__private::Schema::new(); {schema} schema }}).columns() }}).as_slice();
+
+                fn parse_row(
+                    row: &[::std::borrow::Cow<'__src, str>],
+                    positions: &mut ::std::slice::Iter<'_, usize>
+                ) -> ::std::result::Result<Self, ::dataframe::Error> {{
+                    ::std::result::Result::Ok(Self {{ {parse_row} }})
+                }}
+
+                fn parse_vals(
+                    row: &[::std::borrow::Cow<'_, str>],
+                    positions: &mut ::std::slice::Iter<'_, usize>,
+                    data: &mut ::std::vec::Vec<::dataframe::Val>
+                ) -> ::std::result::Result<(), ::dataframe::Error> {{
+                    {parse_vals}
+                    ::std::result::Result::Ok(())
+                }}
+
+                fn to_vals(&self, data: &mut ::std::vec::Vec<::dataframe::Val>) {{
+                    {to_vals}
+                }}
+            }}
 
-                    let file = std::fs::File::open(&path)?;
-                    let mut buf = std::io::BufReader::new(file);
+            impl<{generics}> {name}{generic_args} {where_clause} {{
+                {visibility} fn read_csv<P: ::std::convert::AsRef<::std::path::Path>>(path: P) -> ::std::result::Result<::dataframe::DataFrame, ::dataframe::Error>
+                where
+                    Self: ::dataframe::Record<'static>
+                {{
+                    use ::std::io::Read as _;
 
-                    let mut s = String::new();
+                    let file = ::std::fs::File::open(&path)?;
+                    let mut buf = ::std::io::BufReader::new(file);
+
+                    let mut s = ::std::string::String::new();
                     buf.read_to_string(&mut s)?;
 
                     Self::read_str(s)
                 }}
 
-                {visibility} fn read_str(input: String) -> Result<DataFrame, Error> {{
-                    let mut raw_width = 0;
-                    let mut raw_height = 0;
-                    let raw = input
-                        .lines()
-                        .flat_map(|line| {{
-                            raw_height += 1;
-                            let l = line.split(\",\").map(ToString::to_string).collect::<Vec<_>>();
-                            raw_width = l.len();
-                            l
-                        }})
-                        .collect::<Vec<_>>();
-
-                    let headers = raw[0..raw_width].to_vec();
-                    if {fnames:?}.len() > raw_width {{
-                        let inc_pos = {fnames:?}.iter().position(|fname| !headers.contains(&fname.to_string())).unwrap();
-                        let incompatible = {fnames:?}[inc_pos].to_string();
-                        return Err(Error::IncompatibleStruct {{
-                            struct_fields: {fnames:?}.len(),
-                            csv_columns: raw_width,
-                            incompatible
-                        }})
+                {visibility} fn read_str(input: ::std::string::String) -> ::std::result::Result<::dataframe::DataFrame, ::dataframe::Error>
+                where
+                    Self: ::dataframe::Record<'static>
+                {{
+                    let (headers, rows) = ::dataframe::__private::split(&input)?;
+                    let fields = <Self as ::dataframe::Record<'static>>::SCHEMA.iter().map(|(name, _)| *name).collect::<::std::vec::Vec<_>>();
+                    let positions = ::dataframe::__private::positions(&headers, &fields, {strict})?;
+                    let names = fields.iter().map(|name| ::std::string::ToString::to_string(name)).collect::<::std::vec::Vec<_>>();
+
+                    let mut data = ::std::vec::Vec::with_capacity(rows.len() * names.len());
+                    for row in &rows {{
+                        <Self as ::dataframe::Record<'static>>::parse_vals(row, &mut positions.iter(), &mut data)?;
                     }}
-                    let new_pos = {fnames:?}
+
+                    let width = names.len();
+                    ::std::result::Result::Ok(::dataframe::DataFrame::new(names, data, width, rows.len()))
+                }}
+
+                {visibility} fn read_records<'__src>(input: &'__src str) -> ::std::result::Result<::std::vec::Vec<Self>, ::dataframe::Error>
+                where
+                    Self: ::dataframe::Record<'__src>
+                {{
+                    let (headers, rows) = ::dataframe::__private::split(input)?;
+                    let fields = <Self as ::dataframe::Record<'__src>>::SCHEMA.iter().map(|(name, _)| *name).collect::<::std::vec::Vec<_>>();
+                    let positions = ::dataframe::__private::positions(&headers, &fields, {strict})?;
+
+                    rows.iter()
+                        .map(|row| <Self as ::dataframe::Record<'__src>>::parse_row(row, &mut positions.iter()))
+                        .collect()
+                }}
+
+                {visibility} fn to_dataframe(records: &[Self]) -> ::dataframe::DataFrame
+                where
+                    Self: ::dataframe::Record<'static>
+                {{
+                    let names = <Self as ::dataframe::Record<'static>>::SCHEMA
                         .iter()
-                        .filter_map(|name| headers.iter().position(|header| header == name))
-                        .collect::<Vec<_>>();
-
-                    let mut cursor = 0;
-                    let mut adv = 0;
-                    let slice = raw[raw_width..].to_vec();
-                    let mut filtered_data = Vec::new();
-                    while cursor < slice.len() {{
-                        let pos = new_pos[cursor % new_pos.len()];
-                        filtered_data.push(slice[pos + adv].to_string());
-                        cursor += 1;
-                        if cursor % new_pos.len() == 0 {{
-                            adv += raw_width;
-                        }}
-                        if pos + adv > slice.len() {{ break }}
+                        .map(|(name, _)| ::std::string::ToString::to_string(name))
+                        .collect::<::std::vec::Vec<_>>();
+                    let mut data = ::std::vec::Vec::with_capacity(records.len() * names.len());
+                    for record in records {{
+                        <Self as ::dataframe::Record<'static>>::to_vals(record, &mut data);
                     }}
-                    
-                    let data = filtered_data.iter().enumerate().map(|(i, d)| {{
-                        let ftyp = {ftypes:?}[i % {ftypes:?}.len()];
-                        let val = match ftyp {{
-                            \"f64\" => {{Val::Float64(d.parse::<f64>().unwrap_or_default())}},
-                            \"f32\" => {{Val::Usize(d.parse::<usize>().unwrap_or_default())}},
-                            \"usize\" => {{Val::Usize(d.parse::<usize>().unwrap_or_default())}},
-                            \"isize\" => {{Val::Usize(d.parse::<usize>().unwrap_or_default())}},
-                            \"u128\" => {{Val::Usize(d.parse::<usize>().unwrap_or_default())}},
-                            \"i128\" => {{Val::Usize(d.parse::<usize>().unwrap_or_default())}},
-                            \"u64\" => {{Val::Usize(d.parse::<usize>().unwrap_or_default())}},
-                            \"i64\" => {{Val::Usize(d.parse::<usize>().unwrap_or_default())}},
-                            \"u32\" => {{Val::Usize(d.parse::<usize>().unwrap_or_default())}},
-                            \"i32\" => {{Val::Usize(d.parse::<usize>().unwrap_or_default())}},
-                            \"u16\" => {{Val::Usize(d.parse::<usize>().unwrap_or_default())}},
-                            \"i16\" => {{Val::Usize(d.parse::<usize>().unwrap_or_default())}},
-                            \"u8\" => {{Val::Usize(d.parse::<usize>().unwrap_or_default())}},
-                            \"i8\" => {{Val::Usize(d.parse::<usize>().unwrap_or_default())}},
-                            \"String\" => {{Val::String(d.to_string())}},
-                            other => {{return Err(Error::InvalidDataType(other.to_string()))}}
-                        }};
-                        Ok::<Val, Error>(val)
-                    }}).collect::<Result<Vec<Val>, Error>>()?;
-
-                    let df = DataFrame::new(
-                        {fnames:?}.iter().map(ToString::to_string).collect(),
-                        data,
-                        {fnames:?}.len(),
-                        raw_height - 1
-                    );
-
-                    Ok(df)
+
+                    let width = names.len();
+                    ::dataframe::DataFrame::new(names, data, width, records.len())
                 }}
             }}
-        ").parse().unwrap()
+        ").parse().unwrap();
+
+        Ok(ts)
+    }
+}
+
+impl ParsedTokenStream {
+    fn variants(&self) -> Result<Vec<VariantDef>, ParseError> {
+        if !self.is_enum || self.generics.is_some() {
+            return Err(ParseError::EnumNotFound);
+        }
+        let data = self.data.as_ref().ok_or(ParseError::EnumNotFound)?;
+        data.iter().map(|variant| VariantDef::parse(variant)).collect()
+    }
+
+    fn to_enum_token_stream(&self) -> Result<proc_macro::TokenStream, ParseError> {
+        if self.strict {
+            return Err(ParseError::UnknownAttribute("strict".to_string()));
+        }
+        let visibility = self.visibility.to_str();
+        let name = self.name();
+        let variants = self.variants()?;
+
+        let values = variants.iter().map(|variant| format!("{:?},", variant.value)).collect::<String>();
+        let parse = variants
+            .iter()
+            .map(|VariantDef { name, value }| format!("{value:?} => ::std::result::Result::Ok(Self::{name}),"))
+            .collect::<String>();
+        let to_str = variants
+            .iter()
+            .map(|VariantDef { name, value }| format!("Self::{name} => {value:?},"))
+            .collect::<String>();
+        let codes = variants
+            .iter()
+            .enumerate()
+            .map(|(code, VariantDef { name, .. })| format!("Self::{name} => {code},"))
+            .collect::<String>();
+
+        let ts = format!("
+            impl {name} {{
+                {visibility} const VARIANTS: &'static [&'static str] = &[{values}];
+
+                {visibility} fn as_str(&self) -> &'static str {{
+                    match self {{
+                        {to_str}
+                    }}
+                }}
+            }}
+
+            impl ::dataframe::Field<'_> for {name} {{
+                const DTYPE: ::dataframe::DType = ::dataframe::DType::Categorical;
+
+                fn parse_field(input: ::std::borrow::Cow<'_, str>) -> ::std::result::Result<Self, ::dataframe::Error> {{
+                    match &*input {{
+                        {parse}
+                        other => ::std::result::Result::Err(::dataframe::Error::UnknownVariant {{
+                            value: ::std::string::ToString::to_string(other),
+                            allowed: Self::VARIANTS.iter().map(|v| ::std::string::ToString::to_string(v)).collect(),
+                        }}),
+                    }}
+                }}
+
+                fn parse_val(input: &str) -> ::std::result::Result<::dataframe::Val, ::dataframe::Error> {{
+                    <Self as ::dataframe::Field<'_>>::parse_field(::std::borrow::Cow::Borrowed(input)).map(|variant| ::dataframe::Field::to_val(&variant))
+                }}
+
+                fn to_val(&self) -> ::dataframe::Val {{
+                    static CATEGORIES: ::std::sync::OnceLock<::std::sync::Arc<[::std::string::String]>> = ::std::sync::OnceLock::new();
+                    let code = match self {{
+                        {codes}
+                    }};
+                    ::dataframe::__private::variant(&CATEGORIES, Self::VARIANTS, code)
+                }}
+            }}
+        ").parse().unwrap();
+
+        Ok(ts)
+    }
+}
+
+struct VariantDef {
+    name: String,
+    // the csv representation, the variant name unless renamed
+    value: String,
+}
+
+impl VariantDef {
+    fn parse(variant: &[proc_macro::TokenTree]) -> Result<Self, ParseError> {
+        let mut rename = None;
+        let mut name = None;
+        let mut i = 0;
+        while i < variant.len() {
+            match (&variant[i], variant.get(i + 1)) {
+                (tree, Some(proc_macro::TokenTree::Group(attr))) if is_punct(tree, '#') => {
+                    for arg in attribute_args(attr) {
+                        match &arg[..] {
+                            [proc_macro::TokenTree::Ident(ident), eq, proc_macro::TokenTree::Literal(lit)] if ident.to_string() == "rename" && is_punct(eq, '=') => {
+                                rename = Some(string_literal(lit)?);
+                            },
+                            _ => return Err(ParseError::UnknownAttribute(to_string(&arg))),
+                        }
+                    }
+                    i += 1;
+                },
+                (proc_macro::TokenTree::Ident(ident), next) if name.is_none() => {
+                    if let Some(proc_macro::TokenTree::Group(_)) = next {
+                        return Err(ParseError::NonUnitVariant(ident.to_string()));
+                    }
+                    name = Some(ident.to_string());
+                },
+                // an explicit discriminant
+                (tree, _) if is_punct(tree, '=') => break,
+                _ => {}
+            }
+            i += 1;
+        }
+
+        let name = name.ok_or(ParseError::EnumNotFound)?;
+        let value = rename.unwrap_or_else(|| name.clone());
+
+        Ok(Self { name, value })
+    }
+}
+
+struct FieldDef {
+    name: String,
+    ty: String,
+    // column prefix of a `#[dataframe(flatten)]` field
+    flatten: Option<String>,
+}
+
+impl FieldDef {
+    fn parse(field: &[proc_macro::TokenTree]) -> Result<Self, ParseError> {
+        let colon = colon(field);
+        let name = field[colon - 1].to_string();
+        let ty = to_string(&field[colon + 1..]);
+
+        let mut flatten = false;
+        let mut prefix = None;
+        for (i, tree) in field[..colon].iter().enumerate() {
+            let (true, Some(proc_macro::TokenTree::Group(attr))) = (is_punct(tree, '#'), field.get(i + 1)) else { continue };
+            for arg in attribute_args(attr) {
+                match &arg[..] {
+                    [proc_macro::TokenTree::Ident(ident)] if ident.to_string() == "flatten" => flatten = true,
+                    [proc_macro::TokenTree::Ident(ident), eq, proc_macro::TokenTree::Literal(lit)] if ident.to_string() == "prefix" && is_punct(eq, '=') => {
+                        prefix = Some(string_literal(lit)?);
+                    },
+                    _ => return Err(ParseError::UnknownAttribute(to_string(&arg))),
+                }
+            }
+        }
+
+        let flatten = match (flatten, prefix) {
+            (true, prefix) => Some(prefix.unwrap_or_else(|| format!("{name}."))),
+            (false, Some(_)) => return Err(ParseError::PrefixWithoutFlatten(name)),
+            (false, None) => None,
+        };
+
+        Ok(Self { name, ty, flatten })
+    }
+}
+
+fn to_string(trees: &[proc_macro::TokenTree]) -> String {
+    trees.iter().cloned().collect::<proc_macro::TokenStream>().to_string()
+}
+
+// The value of a string literal, plain with its escapes resolved or raw.
+fn string_literal(lit: &proc_macro::Literal) -> Result<String, ParseError> {
+    let text = lit.to_string();
+    let invalid = || ParseError::ExpectedString(text.clone());
+    if let Some(raw) = text.strip_prefix('r') {
+        let hashes = "#".repeat(raw.len() - raw.trim_start_matches('#').len());
+        return raw
+            .strip_prefix(hashes.as_str())
+            .and_then(|raw| raw.strip_prefix('"'))
+            .and_then(|raw| raw.strip_suffix(hashes.as_str()))
+            .and_then(|raw| raw.strip_suffix('"'))
+            .map(str::to_string)
+            .ok_or_else(invalid);
+    }
+
+    let quoted = text.strip_prefix('"').and_then(|text| text.strip_suffix('"')).ok_or_else(invalid)?;
+    let mut value = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        let escaped = match chars.next().ok_or_else(invalid)? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            '\\' => '\\',
+            '\'' => '\'',
+            '"' => '"',
+            'x' => {
+                let hex = chars.by_ref().take(2).collect::<String>();
+                u8::from_str_radix(&hex, 16).ok().filter(u8::is_ascii).ok_or_else(invalid)? as char
+            },
+            'u' => {
+                let hex = chars.by_ref().skip(1).take_while(|&c| c != '}').filter(|&c| c != '_').collect::<String>();
+                u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32).ok_or_else(invalid)?
+            },
+            // a line continuation skips the newline and the whitespace after it
+            '\n' => {
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                continue;
+            },
+            _ => return Err(invalid()),
+        };
+        value.push(escaped);
+    }
+    Ok(value)
+}
+
+fn is_punct(tree: &proc_macro::TokenTree, c: char) -> bool {
+    matches!(tree, proc_macro::TokenTree::Punct(p) if p.as_char() == c)
+}
+
+// position of the first `c` that isn't nested inside `<...>`; the `>` of
+// an `->` doesn't close anything
+fn position_top_level(trees: &[proc_macro::TokenTree], c: char) -> Option<usize> {
+    let mut depth = 0usize;
+    trees.iter().enumerate().position(|(i, tree)| {
+        let arrow = i > 0 && is_punct(tree, '>') && is_punct(&trees[i - 1], '-');
+        if depth == 0 && is_punct(tree, c) && !arrow {
+            return true;
+        }
+        if is_punct(tree, '<') {
+            depth += 1;
+        } else if is_punct(tree, '>') && !arrow {
+            depth = depth.saturating_sub(1);
+        }
+        false
+    })
+}
+
+fn split_top_level(trees: &[proc_macro::TokenTree]) -> Vec<Vec<proc_macro::TokenTree>> {
+    let mut parts = Vec::new();
+    let mut rest = trees;
+    while !rest.is_empty() {
+        let end = position_top_level(rest, ',').unwrap_or(rest.len());
+        parts.push(rest[..end].to_vec());
+        rest = rest.get(end + 1..).unwrap_or_default();
+    }
+    parts.into_iter().filter(|part| !part.is_empty()).collect()
+}
+
+// the first `:` of a field separates its name (preceded by any attributes
+// and visibility) from its type
+fn colon(field: &[proc_macro::TokenTree]) -> usize {
+    field
+        .iter()
+        .position(|tree| is_punct(tree, ':'))
+        .unwrap_or(1)
+}
+
+// the arguments of a `#[dataframe(...)]` attribute, empty for any other
+// attribute
+fn attribute_args(attr: &proc_macro::Group) -> Vec<Vec<proc_macro::TokenTree>> {
+    let trees = attr.stream().into_iter().collect::<Vec<_>>();
+    match &trees[..] {
+        [proc_macro::TokenTree::Ident(ident), proc_macro::TokenTree::Group(args)] if ident.to_string() == "dataframe" => {
+            let args = args.stream().into_iter().collect::<Vec<_>>();
+            split_top_level(&args)
+        },
+        _ => Vec::new()
     }
 }
 
@@ -196,20 +595,18 @@ impl Cursor {
         let mut visibility = Visibility::Private;
         let mut name: Option<proc_macro::Ident> = None;
         let mut generics: Option<Vec<proc_macro::TokenTree>> = None;
+        let mut where_clause: Option<Vec<proc_macro::TokenTree>> = None;
         let mut data: Option<Vec<Vec<proc_macro::TokenTree>>> = None;
+        let mut strict = false;
+        let mut is_enum = false;
 
         while self.offset < self.buffer.len() {
             match &self.buffer[self.offset] {
-                proc_macro::TokenTree::Group(group) => {
+                proc_macro::TokenTree::Group(group) if group.delimiter() == proc_macro::Delimiter::Brace => {
                     let group_data = group.stream().into_iter().collect::<Vec<_>>();
-                    let fields = group_data.split(|tree| {
-                        match tree {
-                            proc_macro::TokenTree::Punct(punct) => punct.as_char() == ',',
-                            _ => false
-                        }
-                    }).filter(|trees| trees.len() > 0).map(|trees| trees.to_vec()).collect::<Vec<_>>();
-                    data.replace(fields);
+                    data.replace(split_top_level(&group_data));
                 },
+                proc_macro::TokenTree::Group(_) => {},
                 proc_macro::TokenTree::Ident(ident) => {
                     match ident.to_string().as_str() {
                         "pub" => {
@@ -228,35 +625,42 @@ impl Cursor {
                             } else { visibility = Visibility::Pub }
                         }
                         "struct" | "enum" => {
+                            is_enum = ident.to_string() == "enum";
                             self.offset += 1;
                             let proc_macro::TokenTree::Ident(n) = &self.buffer[self.offset] else { continue };
                             name.replace(n.clone());
                         }
+                        "where" => {
+                            let body = self.buffer[self.offset..]
+                                .iter()
+                                .position(|tree| matches!(tree, proc_macro::TokenTree::Group(g) if g.delimiter() == proc_macro::Delimiter::Brace))
+                                .map(|pos| pos + self.offset)
+                                .unwrap_or(self.buffer.len());
+                            where_clause.replace(self.buffer[self.offset..body].to_vec());
+                            self.offset = body;
+                            continue;
+                        }
                         _ => {}
                     }
                 },
+                proc_macro::TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                    if let Some(proc_macro::TokenTree::Group(attr)) = self.buffer.get(self.offset + 1) {
+                        for arg in attribute_args(attr) {
+                            match to_string(&arg).as_str() {
+                                "strict" => strict = true,
+                                arg => return Err(ParseError::UnknownAttribute(arg.to_string())),
+                            }
+                        }
+                        self.offset += 1;
+                    }
+                },
                 proc_macro::TokenTree::Punct(punct) => {
-                    match punct.as_char() {
-                        '<' => {
-                            let  closing = &self.buffer[self.offset..].iter().position(|tree| {
-                                match tree {
-                                    proc_macro::TokenTree::Punct(punct) => {
-                                        punct.as_char() == '>'
-                                    },
-                                    _ => false
-                                }
-                            });
-                            if let Some(closing) = closing {
-                                generics.replace(
-                                    self
-                                        .buffer[self.offset + 1..*closing + self.offset]
-                                        .iter()
-                                        .cloned()
-                                        .collect::<Vec<_>>()
-                                );
-                            } else { continue }
-                        },
-                        _ => {}
+                    if punct.as_char() == '<' && name.is_some() && generics.is_none() {
+                        let rest = &self.buffer[self.offset + 1..];
+                        if let Some(closing) = position_top_level(rest, '>') {
+                            generics.replace(rest[..closing].to_vec());
+                            self.offset += closing + 1;
+                        }
                     }
                 },
                 proc_macro::TokenTree::Literal(_) => {},
@@ -270,15 +674,33 @@ impl Cursor {
             visibility,
             name,
             generics,
+            where_clause,
             data,
+            strict,
+            is_enum,
         })
     }
 }
 
-#[proc_macro_derive(DataFrame)]
+// A derived struct, flattened fields included, holds at most 1024 columns
+// whose names take at most 32 KiB; going over fails to compile wherever its
+// `SCHEMA` gets used.
+#[proc_macro_derive(DataFrame, attributes(dataframe))]
 pub fn derive_dataframe(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let mut cursor = Cursor::new(input);
-    let parsed = cursor.parse().unwrap();
 
-    parsed.into_token_stream()
+    match cursor.parse().and_then(|parsed| parsed.to_token_stream()) {
+        Ok(ts) => ts,
+        Err(err) => format!("::std::compile_error!({:?});", err.to_string()).parse().unwrap(),
+    }
+}
+
+#[proc_macro_derive(DataFrameEnum, attributes(dataframe))]
+pub fn derive_dataframe_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let mut cursor = Cursor::new(input);
+
+    match cursor.parse().and_then(|parsed| parsed.to_enum_token_stream()) {
+        Ok(ts) => ts,
+        Err(err) => format!("::std::compile_error!({:?});", err.to_string()).parse().unwrap(),
+    }
 }
@@ -0,0 +1,119 @@
+use std::fmt::Write;
+
+// How `Val::Bytes` cells are written as text in csv data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BytesEncoding {
+    // lowercase hex, read with or without a `0x` prefix
+    #[default]
+    Hex,
+    // the standard alphabet with `=` padding, read with or without it
+    Base64,
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+// The bytes shown by `Debug` before the rest is elided.
+const PREVIEW_LEN: usize = 16;
+
+impl BytesEncoding {
+    pub fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            BytesEncoding::Hex => hex(bytes),
+            BytesEncoding::Base64 => base64(bytes),
+        }
+    }
+
+    pub fn decode(self, text: &str) -> Option<Vec<u8>> {
+        match self {
+            BytesEncoding::Hex => unhex(text),
+            BytesEncoding::Base64 => unbase64(text),
+        }
+    }
+}
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    bytes.iter().for_each(|b| {
+        let _ = write!(out, "{:02x}", b);
+    });
+    out
+}
+
+fn unhex(text: &str) -> Option<Vec<u8>> {
+    let text = text.strip_prefix("0x").unwrap_or(text);
+    if !text.len().is_multiple_of(2) || !text.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| text.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect()
+}
+
+fn base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn unbase64(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim_end_matches('=');
+    let mut out = Vec::with_capacity(text.len() * 3 / 4);
+    for chunk in text.as_bytes().chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+        let n = chunk.iter().enumerate().try_fold(0u32, |n, (i, c)| {
+            let sextet = BASE64.iter().position(|b| b == c)? as u32;
+            Some(n | sextet << (18 - 6 * i))
+        })?;
+        out.extend(n.to_be_bytes()[1..chunk.len()].iter());
+    }
+    Some(out)
+}
+
+// `0x` and the hex of the first bytes, then the total length when cut short
+pub(crate) fn preview(bytes: &[u8]) -> String {
+    if bytes.len() <= PREVIEW_LEN {
+        format!("0x{}", hex(bytes))
+    } else {
+        format!("0x{}…, {} bytes", hex(&bytes[..PREVIEW_LEN]), bytes.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let bytes = b"Nunez scores".to_vec();
+
+        for encoding in [BytesEncoding::Hex, BytesEncoding::Base64] {
+            for len in 0..bytes.len() {
+                assert_eq!(encoding.decode(&encoding.encode(&bytes[..len])).as_deref(), Some(&bytes[..len]));
+            }
+        }
+        assert_eq!(BytesEncoding::Base64.encode(b"Messi"), "TWVzc2k=");
+        assert_eq!(BytesEncoding::Base64.decode("TWVzc2k"), Some(b"Messi".to_vec()));
+        assert_eq!(BytesEncoding::Hex.decode("0xDEADbeef"), Some(vec![0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(BytesEncoding::Hex.decode("abc"), None);
+        assert_eq!(BytesEncoding::Hex.decode("zz"), None);
+        assert_eq!(BytesEncoding::Base64.decode("TW!z"), None);
+    }
+
+    #[test]
+    fn truncated_preview() {
+        assert_eq!(preview(&[0xca, 0xfe]), "0xcafe");
+        assert_eq!(preview(&[0xff; 20]), format!("0x{}…, 20 bytes", "ff".repeat(16)));
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    hash::{Hash, Hasher},
+    sync::{Arc, OnceLock},
+};
+
+use crate::{DType, Error, Val};
+
+// A string stored as a code into a dictionary of categories, which every
+// value of a column shares. Categoricals compare and hash like the string
+// they stand for, so they also equal a `Val::String` of the same text.
+#[derive(Clone)]
+pub struct Categorical {
+    categories: Arc<[String]>,
+    code: u32,
+}
+
+impl Categorical {
+    pub fn new(categories: Arc<[String]>, code: u32) -> Option<Self> {
+        ((code as usize) < categories.len()).then_some(Self { categories, code })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.categories[self.code as usize]
+    }
+
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+
+    pub fn categories(&self) -> &Arc<[String]> {
+        &self.categories
+    }
+
+    pub fn shares_categories(&self, other: &Categorical) -> bool {
+        Arc::ptr_eq(&self.categories, &other.categories)
+    }
+}
+
+// Dictionary-encodes strings in order of first appearance, nulls stay null.
+pub(crate) fn encode(values: &[Val]) -> Result<Vec<Val>, Error> {
+    let mut index = HashMap::<&str, u32>::new();
+    let mut categories = Vec::new();
+    let codes = values
+        .iter()
+        .map(|val| {
+            let text = match val {
+                Val::Null => return Ok(None),
+                Val::String(s) => s.as_str(),
+                Val::Categorical(c) => c.as_str(),
+                _ => return Err(Error::IncompatibleConversion { from: val.dtype(), to: DType::Categorical }),
+            };
+            let code = *index.entry(text).or_insert_with(|| {
+                categories.push(text.to_string());
+                categories.len() as u32 - 1
+            });
+            Ok(Some(code))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let categories = Arc::<[String]>::from(categories);
+    let values = codes
+        .into_iter()
+        .map(|code| code.map_or(Val::Null, |code| Val::Categorical(Categorical { categories: categories.clone(), code })))
+        .collect();
+    Ok(values)
+}
+
+// The value of variant `code` of a `DataFrameEnum`, every value of the enum
+// sharing the dictionary of its variants kept in `categories`.
+#[doc(hidden)]
+pub fn variant(categories: &'static OnceLock<Arc<[String]>>, variants: &[&str], code: u32) -> Val {
+    let categories = categories.get_or_init(|| variants.iter().map(|variant| variant.to_string()).collect());
+    Val::Categorical(Categorical { categories: categories.clone(), code })
+}
+
+// The dictionary of values that are all categoricals sharing one, or nulls.
+pub(crate) fn shared_categories<'a, I: IntoIterator<Item = &'a Val>>(values: I) -> Option<Arc<[String]>> {
+    let mut categories: Option<&Arc<[String]>> = None;
+    for val in values {
+        match (val, categories) {
+            (Val::Null, _) => {},
+            (Val::Categorical(c), None) => categories = Some(&c.categories),
+            (Val::Categorical(c), Some(shared)) if Arc::ptr_eq(&c.categories, shared) => {},
+            _ => return None,
+        }
+    }
+    categories.cloned()
+}
+
+impl PartialEq for Categorical {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Categorical {}
+
+impl PartialOrd for Categorical {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Categorical {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.shares_categories(other) && self.code == other.code {
+            return Ordering::Equal;
+        }
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for Categorical {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl fmt::Display for Categorical {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// Leaves the dictionary out, every value of a column would print it.
+impl fmt::Debug for Categorical {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Categorical({:?})", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_strings() -> Result<(), Error> {
+        let values = ["Italy", "Portugal", "Italy"].into_iter().map(Val::from).chain([Val::Null]).collect::<Vec<_>>();
+        let encoded = encode(&values)?;
+
+        assert_eq!(encoded, values);
+        assert!(matches!(&encoded[2], Val::Categorical(c) if c.code() == 0 && c.categories().len() == 2));
+        assert!(encoded[3].is_null());
+        assert!(shared_categories(&encoded).is_some());
+        assert!(shared_categories(&values).is_none());
+        assert_eq!(format!("{:?}", encoded[1]), "Categorical(\"Portugal\")");
+        assert!(matches!(encode(&[Val::Int8(1)]), Err(Error::IncompatibleConversion { to: DType::Categorical, .. })));
+
+        Ok(())
+    }
+}
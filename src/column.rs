@@ -0,0 +1,383 @@
+use std::ops::{Add, BitAnd, BitOr, Div, Mul, Neg, Not, Rem, Sub};
+
+use crate::{Date, Error, Rounding, Time, Val};
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Column {
+    name: String,
+    values: Vec<Val>,
+}
+
+impl Column {
+    pub fn new<S: Into<String>>(name: S, values: Vec<Val>) -> Self {
+        Self {
+            name: name.into(),
+            values,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn rename<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn values(&self) -> &[Val] {
+        &self.values
+    }
+
+    pub fn into_values(self) -> Vec<Val> {
+        self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Val> {
+        self.values.iter()
+    }
+
+    // applies `f` to every value, keeping the name
+    pub fn try_map<F: FnMut(&Val) -> Result<Val, Error>>(&self, f: F) -> Result<Column, Error> {
+        let values = self.values.iter().map(f).collect::<Result<Vec<_>, Error>>()?;
+        Ok(Column::new(self.name.clone(), values))
+    }
+
+    // combines the values of both columns pairwise, keeping the name of `self`
+    pub fn try_zip<F: FnMut(&Val, &Val) -> Result<Val, Error>>(&self, other: &Column, mut f: F) -> Result<Column, Error> {
+        if self.len() != other.len() {
+            return Err(Error::LengthMismatch { expected: self.len(), found: other.len() });
+        }
+        let values = self
+            .values
+            .iter()
+            .zip(&other.values)
+            .map(|(lhs, rhs)| f(lhs, rhs))
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(Column::new(self.name.clone(), values))
+    }
+
+    // a string column as a categorical one, sharing a single dictionary
+    pub fn to_categorical(&self) -> Result<Column, Error> {
+        let values = crate::categorical::encode(&self.values)?;
+        Ok(Column::new(self.name.clone(), values))
+    }
+
+    // a categorical column back as plain strings
+    pub fn to_strings(&self) -> Result<Column, Error> {
+        self.try_map(|val| match val {
+            Val::Null => Ok(Val::Null),
+            _ => String::try_from(val).map(Val::String),
+        })
+    }
+
+    // builds a boolean column from a predicate, e.g. to filter a dataframe
+    pub fn mask<F: FnMut(&Val) -> bool>(&self, mut f: F) -> Column {
+        let values = self.values.iter().map(|val| Val::Bool(f(val))).collect();
+        Column::new(self.name.clone(), values)
+    }
+
+    // the non-null values of a boolean column
+    fn bools(&self) -> impl Iterator<Item = Result<bool, Error>> + '_ {
+        self.values
+            .iter()
+            .filter(|val| !val.is_null())
+            .map(bool::try_from)
+    }
+
+    // whether any non-null value is true, false for an empty column
+    pub fn any(&self) -> Result<bool, Error> {
+        let mut any = false;
+        for b in self.bools() {
+            any |= b?;
+        }
+        Ok(any)
+    }
+
+    // whether every non-null value is true, true for an empty column
+    pub fn all(&self) -> Result<bool, Error> {
+        let mut all = true;
+        for b in self.bools() {
+            all &= b?;
+        }
+        Ok(all)
+    }
+
+    // rounds a decimal column to `scale` digits after the point
+    pub fn round(&self, scale: u8, rounding: Rounding) -> Result<Column, Error> {
+        self.try_map(|val| match val {
+            Val::Null => Ok(Val::Null),
+            Val::Decimal(d) => d.round(scale, rounding).map(Val::Decimal),
+            _ => Err(Error::UnsupportedOperation(format!("round {}", val.dtype()))),
+        })
+    }
+
+    fn date_part<F: Fn(Date) -> Val>(&self, part: &str, f: F) -> Result<Column, Error> {
+        self.try_map(|val| match val {
+            Val::Null => Ok(Val::Null),
+            Val::Date(date) => Ok(f(*date)),
+            Val::Datetime(datetime) => Ok(f(datetime.date())),
+            _ => Err(Error::UnsupportedOperation(format!("{part} of {}", val.dtype()))),
+        })
+    }
+
+    fn time_part<F: Fn(Time) -> Val>(&self, part: &str, f: F) -> Result<Column, Error> {
+        self.try_map(|val| match val {
+            Val::Null => Ok(Val::Null),
+            Val::Time(time) => Ok(f(*time)),
+            Val::Datetime(datetime) => Ok(f(datetime.time())),
+            _ => Err(Error::UnsupportedOperation(format!("{part} of {}", val.dtype()))),
+        })
+    }
+
+    // the parts of a date or datetime column, in the datetime's own offset
+    pub fn year(&self) -> Result<Column, Error> {
+        self.date_part("year", |date| Val::Int32(date.year()))
+    }
+
+    pub fn month(&self) -> Result<Column, Error> {
+        self.date_part("month", |date| Val::Uint8(date.month() as u8))
+    }
+
+    pub fn day(&self) -> Result<Column, Error> {
+        self.date_part("day", |date| Val::Uint8(date.day() as u8))
+    }
+
+    // from 1 for Monday to 7 for Sunday
+    pub fn weekday(&self) -> Result<Column, Error> {
+        self.date_part("weekday", |date| Val::Uint8(date.weekday() as u8))
+    }
+
+    // the parts of a time or datetime column
+    pub fn hour(&self) -> Result<Column, Error> {
+        self.time_part("hour", |time| Val::Uint8(time.hour() as u8))
+    }
+
+    pub fn minute(&self) -> Result<Column, Error> {
+        self.time_part("minute", |time| Val::Uint8(time.minute() as u8))
+    }
+
+    pub fn second(&self) -> Result<Column, Error> {
+        self.time_part("second", |time| Val::Uint8(time.second() as u8))
+    }
+}
+
+impl<'a> IntoIterator for &'a Column {
+    type Item = &'a Val;
+    type IntoIter = std::slice::Iter<'a, Val>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.iter()
+    }
+}
+
+// Element-wise arithmetic between two columns of the same length, or between
+// a column and a scalar `Val` broadcast to every row.
+macro_rules! impl_column_op {
+    ($($trait:ident, $fn:ident;)*) => {
+        $(
+            impl $trait<&Column> for &Column {
+                type Output = Result<Column, Error>;
+
+                fn $fn(self, rhs: &Column) -> Self::Output {
+                    self.try_zip(rhs, |l, r| $trait::$fn(l, r))
+                }
+            }
+
+            impl $trait<Column> for Column {
+                type Output = Result<Column, Error>;
+
+                fn $fn(self, rhs: Column) -> Self::Output {
+                    $trait::$fn(&self, &rhs)
+                }
+            }
+
+            impl $trait<&Val> for &Column {
+                type Output = Result<Column, Error>;
+
+                fn $fn(self, rhs: &Val) -> Self::Output {
+                    self.try_map(|l| $trait::$fn(l, rhs))
+                }
+            }
+
+            impl $trait<Val> for &Column {
+                type Output = Result<Column, Error>;
+
+                fn $fn(self, rhs: Val) -> Self::Output {
+                    $trait::$fn(self, &rhs)
+                }
+            }
+
+            impl $trait<Val> for Column {
+                type Output = Result<Column, Error>;
+
+                fn $fn(self, rhs: Val) -> Self::Output {
+                    $trait::$fn(&self, &rhs)
+                }
+            }
+
+            impl $trait<&Column> for &Val {
+                type Output = Result<Column, Error>;
+
+                fn $fn(self, rhs: &Column) -> Self::Output {
+                    rhs.try_map(|r| $trait::$fn(self, r))
+                }
+            }
+
+            impl $trait<Column> for Val {
+                type Output = Result<Column, Error>;
+
+                fn $fn(self, rhs: Column) -> Self::Output {
+                    $trait::$fn(&self, &rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_column_op! {
+    Add, add;
+    Sub, sub;
+    Mul, mul;
+    Div, div;
+    Rem, rem;
+    BitAnd, bitand;
+    BitOr, bitor;
+}
+
+impl Neg for &Column {
+    type Output = Result<Column, Error>;
+
+    fn neg(self) -> Self::Output {
+        self.try_map(|val| -val)
+    }
+}
+
+impl Neg for Column {
+    type Output = Result<Column, Error>;
+
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+impl Not for &Column {
+    type Output = Result<Column, Error>;
+
+    fn not(self) -> Self::Output {
+        self.try_map(|val| !val)
+    }
+}
+
+impl Not for Column {
+    type Output = Result<Column, Error>;
+
+    fn not(self) -> Self::Output {
+        !&self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DType;
+
+    fn goals() -> Column {
+        Column::new("goals", vec![Val::Int64(66), Val::Int64(3), Val::Int64(6969)])
+    }
+
+    fn xg() -> Column {
+        Column::new("xg", vec![Val::Float64(66.0), Val::Float64(-0.5), Val::Float64(69.69)])
+    }
+
+    #[test]
+    fn column_arithmetic() -> Result<(), Error> {
+        let ratio = (goals() / xg())?;
+
+        assert_eq!(ratio.name(), "goals");
+        assert_eq!(ratio.values(), &[Val::Float64(1.0), Val::Float64(-6.0), Val::Float64(100.0)]);
+        assert!((&goals() - &goals())?.iter().all(|val| val == &Val::Int64(0)));
+        assert_eq!((-goals())?.values()[0], Val::Int64(-66));
+
+        Ok(())
+    }
+
+    #[test]
+    fn broadcast_scalar() -> Result<(), Error> {
+        assert_eq!((goals() * Val::Uint8(2))?.values(), &[Val::Int64(132), Val::Int64(6), Val::Int64(13938)]);
+        assert_eq!((Val::Int64(7000) - goals())?.values()[2], Val::Int64(31));
+        assert!(matches!(goals() + Val::String("x".into()), Err(Error::UnsupportedOperation(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn length_mismatch() {
+        let short = Column::new("short", vec![Val::Int64(1)]);
+        assert!(matches!(&goals() + &short, Err(Error::LengthMismatch { expected: 3, found: 1 })));
+    }
+
+    #[test]
+    fn boolean_column() -> Result<(), Error> {
+        let scored = goals().mask(|val| val > &Val::Int64(5));
+        let flags = Column::new("flags", vec![Val::Bool(true), Val::Null, Val::Bool(false)]);
+
+        assert_eq!(scored.values(), &[Val::Bool(true), Val::Bool(false), Val::Bool(true)]);
+        assert_eq!((&scored & &flags)?.values(), &[Val::Bool(true), Val::Bool(false), Val::Bool(false)]);
+        assert_eq!((!&flags)?.values(), &[Val::Bool(false), Val::Null, Val::Bool(true)]);
+        assert!(flags.any()?);
+        assert!(!flags.all()?);
+        assert_eq!(scored.sum()?, Val::Usize(2));
+        assert!(matches!(goals().any(), Err(Error::IncompatibleConversion { to: DType::Bool, .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn temporal_parts() -> Result<(), Error> {
+        let kickoff = Column::new("kickoff", vec![
+            Val::Datetime("2024-05-12T20:45:00+02:00".parse()?),
+            Val::Null,
+            Val::Date("2023-12-31".parse()?),
+        ]);
+
+        assert_eq!(kickoff.year()?.values(), &[Val::Int32(2024), Val::Null, Val::Int32(2023)]);
+        assert_eq!(kickoff.weekday()?.values()[0], Val::Uint8(7));
+        assert!(matches!(kickoff.hour(), Err(Error::UnsupportedOperation(_))));
+        assert!(matches!(goals().month(), Err(Error::UnsupportedOperation(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_decimals() -> Result<(), Error> {
+        let prices = Column::new("price", vec![Val::Decimal("2.345".parse()?), Val::Null, Val::Decimal("-2.345".parse()?)]);
+
+        assert_eq!(prices.round(2, Rounding::HalfEven)?.values()[0].to_string(), "2.34");
+        assert_eq!(prices.round(2, Rounding::HalfUp)?.values()[2].to_string(), "-2.35");
+        assert!(matches!(xg().round(1, Rounding::Down), Err(Error::UnsupportedOperation(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn categorical_roundtrip() -> Result<(), Error> {
+        let nationality = Column::new("nationality", ["Italy", "Portugal", "Italy"].into_iter().map(Val::from).collect());
+        let categorical = nationality.to_categorical()?;
+
+        assert!(categorical.iter().all(|val| val.dtype() == DType::Categorical));
+        assert_eq!(categorical, nationality);
+        assert_eq!(categorical.to_strings()?.values(), nationality.values());
+        assert!(matches!(goals().to_strings(), Err(Error::IncompatibleConversion { .. })));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,276 @@
+use crate::{Categorical, Date, Struct, Datetime, Decimal, DType, Duration, Error, Time, Val};
+
+impl From<String> for Val {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&String> for Val {
+    fn from(value: &String) -> Self {
+        Self::String(value.clone())
+    }
+}
+
+impl From<&str> for Val {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl TryFrom<&Val> for Vec<u8> {
+    type Error = Error;
+    fn try_from(value: &Val) -> Result<Self, Self::Error> {
+        match value {
+            Val::Bytes(b) => Ok(b.clone()),
+            other => Err(Error::IncompatibleConversion { from: other.dtype(), to: DType::Bytes })
+        }
+    }
+}
+
+impl From<Struct> for Val {
+    fn from(value: Struct) -> Self {
+        Self::Struct(value)
+    }
+}
+
+impl From<Categorical> for Val {
+    fn from(value: Categorical) -> Self {
+        Self::Categorical(value)
+    }
+}
+
+impl From<bool> for Val {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl<T: Into<Val>> From<Vec<T>> for Val {
+    fn from(value: Vec<T>) -> Self {
+        Self::List(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Val>> From<Option<T>> for Val {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Val::Null, Into::into)
+    }
+}
+
+impl TryFrom<&Val> for String {
+    type Error = Error;
+    fn try_from(value: &Val) -> Result<Self, Self::Error> {
+        match value {
+            Val::String(s) => Ok(s.to_owned()),
+            Val::Categorical(c) => Ok(c.as_str().to_owned()),
+            other => Err(Error::IncompatibleConversion { from: other.dtype(), to: DType::String })
+        }
+    }
+}
+
+impl TryFrom<&Val> for bool {
+    type Error = Error;
+    fn try_from(value: &Val) -> Result<Self, Self::Error> {
+        match value {
+            Val::Bool(b) => Ok(*b),
+            other => Err(Error::IncompatibleConversion { from: other.dtype(), to: DType::Bool })
+        }
+    }
+}
+
+macro_rules! impl_temporal {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl From<$ty> for Val {
+                fn from(value: $ty) -> Self {
+                    Self::$ty(value)
+                }
+            }
+
+            impl TryFrom<&Val> for $ty {
+                type Error = Error;
+                fn try_from(value: &Val) -> Result<Self, Self::Error> {
+                    match value {
+                        Val::$ty(v) => Ok(*v),
+                        other => Err(Error::IncompatibleConversion { from: other.dtype(), to: DType::$ty })
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_temporal!(Date, Time, Datetime, Duration);
+
+impl From<Decimal> for Val {
+    fn from(value: Decimal) -> Self {
+        Self::Decimal(value)
+    }
+}
+
+// Integers convert into a decimal of scale 0.
+impl TryFrom<&Val> for Decimal {
+    type Error = Error;
+    fn try_from(value: &Val) -> Result<Self, Self::Error> {
+        match value {
+            Val::Decimal(d) => Ok(*d),
+            _ if value.dtype().is_numeric() && !value.dtype().is_float() => i128::try_from(value).and_then(Decimal::from_int),
+            _ => Err(Error::IncompatibleConversion { from: value.dtype(), to: DType::Decimal { precision: 38, scale: 0 } }),
+        }
+    }
+}
+
+// Integers convert into any integer type able to hold them, and into a float
+// type when it represents them exactly. Floats convert into an integer type
+// only when integral and in range.
+macro_rules! impl_int {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$ty> for Val {
+                fn from(value: $ty) -> Self {
+                    Self::$variant(value)
+                }
+            }
+
+            impl TryFrom<&Val> for $ty {
+                type Error = Error;
+                fn try_from(value: &Val) -> Result<Self, Self::Error> {
+                    let inexact = || Error::InexactConversion { value: value.to_string(), to: DType::$variant };
+                    match *value {
+                        Val::Isize(n) => n.try_into().map_err(|_| inexact()),
+                        Val::Usize(n) => n.try_into().map_err(|_| inexact()),
+                        Val::Int128(n) => n.try_into().map_err(|_| inexact()),
+                        Val::UInt128(n) => n.try_into().map_err(|_| inexact()),
+                        Val::Int64(n) => n.try_into().map_err(|_| inexact()),
+                        Val::Uint64(n) => n.try_into().map_err(|_| inexact()),
+                        Val::Int32(n) => n.try_into().map_err(|_| inexact()),
+                        Val::Uint32(n) => n.try_into().map_err(|_| inexact()),
+                        Val::Int16(n) => n.try_into().map_err(|_| inexact()),
+                        Val::Uint16(n) => n.try_into().map_err(|_| inexact()),
+                        Val::Int8(n) => n.try_into().map_err(|_| inexact()),
+                        Val::Uint8(n) => n.try_into().map_err(|_| inexact()),
+                        Val::Decimal(d) => d.to_i128().ok_or_else(inexact)?.try_into().map_err(|_| inexact()),
+                        Val::Float64(_) | Val::Float32(_) => {
+                            let f = value.as_f64().unwrap_or(f64::NAN);
+                            let n = f as $ty;
+                            if f.fract() == 0.0 && (n as f64) == f && Val::from(n) == *value {
+                                Ok(n)
+                            } else {
+                                Err(inexact())
+                            }
+                        },
+                        ref other => Err(Error::IncompatibleConversion { from: other.dtype(), to: DType::$variant }),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_int! {
+    isize => Isize,
+    usize => Usize,
+    i128 => Int128,
+    u128 => UInt128,
+    i64 => Int64,
+    u64 => Uint64,
+    i32 => Int32,
+    u32 => Uint32,
+    i16 => Int16,
+    u16 => Uint16,
+    i8 => Int8,
+    u8 => Uint8,
+}
+
+macro_rules! impl_float {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$ty> for Val {
+                fn from(value: $ty) -> Self {
+                    Self::$variant(value)
+                }
+            }
+
+            impl TryFrom<&Val> for $ty {
+                type Error = Error;
+                fn try_from(value: &Val) -> Result<Self, Self::Error> {
+                    let Some(f) = value.as_f64() else {
+                        return Err(Error::IncompatibleConversion { from: value.dtype(), to: DType::$variant });
+                    };
+                    let n = f as $ty;
+                    if f.is_nan() || Val::from(n) == *value {
+                        Ok(n)
+                    } else {
+                        Err(Error::InexactConversion { value: value.to_string(), to: DType::$variant })
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_float! {
+    f64 => Float64,
+    f32 => Float32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widen() -> Result<(), Error> {
+        assert_eq!(i64::try_from(&Val::Int32(5))?, 5);
+        assert_eq!(u128::try_from(&Val::Uint8(255))?, 255);
+        assert_eq!(f64::try_from(&Val::Int32(-7))?, -7.0);
+        assert_eq!(f64::try_from(&Val::Float32(0.5))?, 0.5);
+        assert_eq!(i8::try_from(&Val::Float64(-3.0))?, -3);
+        assert!(f32::try_from(&Val::Float64(f64::NAN))?.is_nan());
+
+        Ok(())
+    }
+
+    #[test]
+    fn narrow() {
+        assert!(matches!(
+            u8::try_from(&Val::Int64(256)),
+            Err(Error::InexactConversion { to: DType::Uint8, .. })
+        ));
+        assert!(usize::try_from(&Val::Int8(-1)).is_err());
+        assert!(i64::try_from(&Val::Float64(2.5)).is_err());
+        assert!(i64::try_from(&Val::Float64(1e300)).is_err());
+        assert!(f32::try_from(&Val::Float64(0.1)).is_err());
+        assert!(f64::try_from(&Val::Int64(i64::MAX - 1)).is_err());
+        assert!(matches!(
+            i64::try_from(&Val::String("5".into())),
+            Err(Error::IncompatibleConversion { from: DType::String, to: DType::Int64 })
+        ));
+        assert!(String::try_from(&Val::Null).is_err());
+    }
+
+    #[test]
+    fn decimal() -> Result<(), Error> {
+        let price = Val::Decimal("19.00".parse()?);
+
+        assert_eq!(i64::try_from(&price)?, 19);
+        assert_eq!(f64::try_from(&Val::Decimal("0.5".parse()?))?, 0.5);
+        assert_eq!(Decimal::try_from(&Val::Uint8(7))?.to_string(), "7");
+        assert!(matches!(u8::try_from(&Val::Decimal("1.5".parse()?)), Err(Error::InexactConversion { .. })));
+        assert!(matches!(Decimal::try_from(&Val::Float64(1.5)), Err(Error::IncompatibleConversion { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn from_primitives() {
+        assert!(matches!(Val::from(3u16), Val::Uint16(3)));
+        assert!(matches!(Val::from(-3isize), Val::Isize(-3)));
+        assert!(matches!(Val::from(0.5f32), Val::Float32(_)));
+        assert_eq!(Val::from("Nunez"), Val::String("Nunez".into()));
+        assert_eq!(Val::from(Some(66u8)), Val::Uint8(66));
+        assert_eq!(Val::from(None::<u8>), Val::Null);
+        assert_eq!(Val::from(true), Val::Bool(true));
+        assert!(bool::try_from(&Val::Int8(1)).is_err());
+    }
+}
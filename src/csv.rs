@@ -0,0 +1,308 @@
+use std::{borrow::Cow, slice::Iter};
+
+use crate::{
+    temporal::{DATETIME_FORMATS, DATE_FORMATS, TIME_FORMATS},
+    BytesEncoding, Date, Datetime, DType, Error, Time, Val,
+};
+
+// How cells are turned into values when reading csv data.
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    // cells read as `Val::Bool`, compared case-insensitively; only `true`
+    // and `false` unless tokens like `yes`/`no` or `1`/`0` are added
+    pub true_values: Vec<String>,
+    pub false_values: Vec<String>,
+    // format strings tried in order for temporal cells, see `Date::parse_from_str`
+    pub datetime_formats: Vec<String>,
+    pub date_formats: Vec<String>,
+    pub time_formats: Vec<String>,
+    // columns read as the given type instead of an inferred one
+    pub schema: Vec<(String, DType)>,
+    // how bytes cells are read, when the schema asks for them, and written
+    pub bytes_encoding: BytesEncoding,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            true_values: vec!["true".to_string()],
+            false_values: vec!["false".to_string()],
+            datetime_formats: DATETIME_FORMATS.iter().map(|f| f.to_string()).collect(),
+            date_formats: DATE_FORMATS.iter().map(|f| f.to_string()).collect(),
+            time_formats: TIME_FORMATS.iter().map(|f| f.to_string()).collect(),
+            schema: Vec::new(),
+            bytes_encoding: BytesEncoding::default(),
+        }
+    }
+}
+
+impl CsvOptions {
+    pub(crate) fn parse_bool(&self, value: &str) -> Option<bool> {
+        if self.true_values.iter().any(|t| t.eq_ignore_ascii_case(value)) {
+            Some(true)
+        } else if self.false_values.iter().any(|f| f.eq_ignore_ascii_case(value)) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn parse_datetime(&self, value: &str) -> Option<Datetime> {
+        self.datetime_formats.iter().find_map(|f| Datetime::parse_from_str(value, f))
+    }
+
+    pub(crate) fn parse_date(&self, value: &str) -> Option<Date> {
+        self.date_formats.iter().find_map(|f| Date::parse_from_str(value, f))
+    }
+
+    pub(crate) fn parse_time(&self, value: &str) -> Option<Time> {
+        self.time_formats.iter().find_map(|f| Time::parse_from_str(value, f))
+    }
+
+    pub(crate) fn parse_temporal(&self, value: &str) -> Option<Val> {
+        self.parse_datetime(value)
+            .map(Val::Datetime)
+            .or_else(|| self.parse_date(value).map(Val::Date))
+            .or_else(|| self.parse_time(value).map(Val::Time))
+    }
+
+    // the type the schema gives to each header, if any
+    pub(crate) fn dtypes<H: AsRef<str>>(&self, headers: &[H]) -> Result<Vec<Option<DType>>, Error> {
+        let mut dtypes = vec![None; headers.len()];
+        for (name, dtype) in &self.schema {
+            let pos = headers
+                .iter()
+                .position(|h| h.as_ref() == name)
+                .ok_or_else(|| Error::HeaderNotFound(name.clone()))?;
+            dtypes[pos] = Some(*dtype);
+        }
+        Ok(dtypes)
+    }
+}
+
+// Splits csv input into its header row and data rows. Quoted cells may hold
+// commas, newlines and `""` escaped quotes, as in RFC 4180. Every cell is
+// borrowed from `input`, except quoted ones whose escapes had to be undone.
+pub fn split(input: &str) -> Result<(Cells<'_>, Vec<Cells<'_>>), Error> {
+    let mut records = Records { input, pos: 0 };
+    let headers = records.next().transpose()?.unwrap_or_default();
+    let rows = records
+        .enumerate()
+        .map(|(i, row)| {
+            let row = row?;
+            if row.len() != headers.len() {
+                return Err(Error::RowLength { row: i, expected: headers.len(), found: row.len() });
+            }
+            Ok(row)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok((headers, rows))
+}
+
+// The cells of one csv record.
+pub type Cells<'src> = Vec<Cow<'src, str>>;
+
+// The records of csv input.
+struct Records<'src> {
+    input: &'src str,
+    pos: usize,
+}
+
+impl<'src> Records<'src> {
+    // Reads the cell at `pos` along with the comma or line break after it,
+    // returning whether that ended the record.
+    fn cell(&mut self) -> Result<(Cow<'src, str>, bool), Error> {
+        let rest = &self.input[self.pos..];
+        let malformed = || Error::MalformedQuote(rest.lines().next().unwrap_or_default().to_string());
+
+        let (cell, len) = match rest.strip_prefix('"') {
+            Some(quoted) => {
+                // a closing quote is one that isn't doubled
+                let mut end = 0;
+                let mut escaped = false;
+                loop {
+                    let i = quoted[end..].find('"').ok_or_else(malformed)?;
+                    if !quoted[end + i + 1..].starts_with('"') {
+                        end += i;
+                        break;
+                    }
+                    end += i + 2;
+                    escaped = true;
+                }
+                let cell = &quoted[..end];
+                let cell = if escaped { Cow::Owned(cell.replace("\"\"", "\"")) } else { Cow::Borrowed(cell) };
+                (cell, end + 2)
+            }
+            None => {
+                let end = rest.find([',', '\n']).unwrap_or(rest.len());
+                let cell = &rest[..end];
+                let cell = if rest[end..].starts_with('\n') { cell.strip_suffix('\r').unwrap_or(cell) } else { cell };
+                (Cow::Borrowed(cell), end)
+            }
+        };
+
+        let after = &rest[len..];
+        let (delimiter, last) = if after.starts_with(',') {
+            (1, false)
+        } else if after.starts_with('\n') {
+            (1, true)
+        } else if after.starts_with("\r\n") {
+            (2, true)
+        } else if after.is_empty() {
+            (0, true)
+        } else {
+            return Err(malformed());
+        };
+        self.pos += len + delimiter;
+        Ok((cell, last))
+    }
+}
+
+impl<'src> Iterator for Records<'src> {
+    type Item = Result<Cells<'src>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.input.len() {
+            return None;
+        }
+
+        let mut row = Vec::new();
+        loop {
+            match self.cell() {
+                Ok((cell, last)) => {
+                    row.push(cell);
+                    if last {
+                        return Some(Ok(row));
+                    }
+                }
+                Err(err) => {
+                    // nothing after a malformed cell can be trusted
+                    self.pos = self.input.len();
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+// Finds the csv column of every struct field. Every field needs a column of
+// the same name; unless `strict`, the csv may carry columns the struct
+// doesn't know about.
+pub fn positions<H: AsRef<str>>(headers: &[H], fields: &[&str], strict: bool) -> Result<Vec<usize>, Error> {
+    let missing = fields
+        .iter()
+        .filter(|field| !headers.iter().any(|header| header.as_ref() == **field))
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    let extra = headers
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|header| strict && !fields.contains(header))
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    if !missing.is_empty() || !extra.is_empty() {
+        return Err(Error::IncompatibleStruct { missing, extra });
+    }
+
+    Ok(fields
+        .iter()
+        .filter_map(|field| headers.iter().position(|header| header.as_ref() == *field))
+        .collect())
+}
+
+// The next cell of `row` according to `positions`.
+pub fn cell<'row, 'src>(row: &'row [Cow<'src, str>], positions: &mut Iter<'_, usize>) -> Result<&'row Cow<'src, str>, Error> {
+    positions
+        .next()
+        .and_then(|&pos| row.get(pos))
+        .ok_or_else(|| Error::Other("Row has fewer cells than the struct has fields".to_string()))
+}
+
+pub(crate) fn write_cell(val: &Val, options: &CsvOptions, out: &mut String) {
+    let cell = match val {
+        Val::Null => String::new(),
+        Val::Bytes(b) => options.bytes_encoding.encode(b),
+        Val::String(s) => s.clone(),
+        Val::Categorical(c) => c.as_str().to_string(),
+        other => other.to_string(),
+    };
+
+    if cell.contains([',', '"', '\n', '\r']) {
+        out.push('"');
+        out.push_str(&cell.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(&cell);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_cell_default(val: &Val, out: &mut String) {
+        write_cell(val, &CsvOptions::default(), out)
+    }
+
+    #[test]
+    fn split_rows() -> Result<(), Error> {
+        let (headers, rows) = split("name,goals\nMessi,66\nNunez,6969\n")?;
+
+        assert_eq!(headers, ["name", "goals"]);
+        assert_eq!(rows, [["Messi", "66"], ["Nunez", "6969"]]);
+        assert!(matches!(
+            split("name,goals\nMessi"),
+            Err(Error::RowLength { row: 0, expected: 2, found: 1 })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn split_quoted() -> Result<(), Error> {
+        let (headers, rows) = split("\"name\",club\r\n\"Nunez, Darwin\",\"\"\"Liverpool\"\"\nFC\"\r\n\"\",\r\n")?;
+
+        assert_eq!(headers, ["name", "club"]);
+        assert_eq!(rows, [["Nunez, Darwin", "\"Liverpool\"\nFC"], ["", ""]]);
+        assert!(matches!(rows[0][0], Cow::Borrowed(_)));
+        assert!(matches!(rows[0][1], Cow::Owned(_)));
+        assert!(matches!(split("name\n\"Messi\n"), Err(Error::MalformedQuote(_))));
+        assert!(matches!(split("name,club\n\"Messi\"x,Inter\n"), Err(Error::MalformedQuote(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn field_positions() -> Result<(), Error> {
+        let headers = ["name", "nationality", "xg", "goals"];
+
+        assert_eq!(positions(&headers, &["goals", "name"], false)?, [3, 0]);
+        assert_eq!(positions(&headers, &["name", "nationality", "goals", "xg"], true)?, [0, 1, 3, 2]);
+
+        let Err(Error::IncompatibleStruct { missing, extra }) = positions(&headers, &["name", "club", "age"], true) else {
+            panic!("club and age aren't in the headers");
+        };
+        assert_eq!(missing, ["club", "age"]);
+        assert_eq!(extra, ["nationality", "xg", "goals"]);
+
+        let Err(Error::IncompatibleStruct { missing, extra }) = positions(&headers, &["name", "club"], false) else {
+            panic!("club isn't in the headers");
+        };
+        assert_eq!(missing, ["club"]);
+        assert!(extra.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn quote_cells() {
+        let mut out = String::new();
+        write_cell_default(&Val::String("Nunez, Darwin".to_string()), &mut out);
+        write_cell_default(&Val::String("6\"9".to_string()), &mut out);
+        write_cell_default(&Val::Float64(-0.5), &mut out);
+
+        assert_eq!(out, "\"Nunez, Darwin\"\"6\"\"9\"-0.5");
+    }
+}
@@ -1,61 +1,17 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     io::{BufReader, Read},
     path::Path
 };
 
-use crate::{Val, Error};
+use crate::{Column, CsvOptions, DType, Val, Error};
 
 #[derive(Clone, Default)]
 pub struct DataFrame {
-    headers: Vec<String>,
-    data: Vec<Val>,
-    width: usize,
-    height: usize
-}
-
-// FIXME: make it better, i find it kinda messy
-impl std::fmt::Debug for DataFrame {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let lens = self.headers.iter().filter_map(|header| {
-            let Some(col) = self.col(header) else { return None };
-            let len = col.iter().map(|val| val.to_string().len()).max();
-            len
-        }).collect::<Vec<_>>();
-        printh_borders(&lens, f)?;
-        self.headers.iter().zip(&lens).enumerate().try_for_each(|(i, (header, len))| {
-            let spacing = len + 5 - header.len();
-            if i % self.width == 0 {
-                write!(f, "\n| {}{:>spacing$}", header, "| ")
-            } else {
-                write!(f, "{}{:>spacing$}", header, "| ")
-            }
-        })?;
-        writeln!(f, "")?;
-        printh_borders(&lens, f)?;
-        self.data.iter().enumerate().try_for_each(|(i, d)| {
-            let spacing = lens[i % self.width] + 5 - d.to_string().len();
-            if i % self.width == 0 {
-                write!(f, "\n| {}{:>spacing$}", d, "| ")
-            } else if i == self.data.len() - 1 {
-                write!(f, "{}{:>spacing$}\n", d, "| ")
-            } else {
-                write!(f, "{}{:>spacing$}", d, "| ")
-            }
-        })?;
-        printh_borders(&lens, f)
-    }
-}
-
-fn printh_borders(lens: &Vec<usize>, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    lens.iter().enumerate().try_for_each(|(i, spacing)| {
-        let spacing = spacing + 4;
-        if i == lens.len() - 1 {
-            write!(f, "+{:->spacing$}+", "")
-        } else {
-            write!(f, "+{:->spacing$}", "")
-        }
-    })
+    pub(crate) headers: Vec<String>,
+    pub(crate) data: Vec<Val>,
+    pub(crate) width: usize,
+    pub(crate) height: usize
 }
 
 impl DataFrame {
@@ -69,39 +25,81 @@ impl DataFrame {
     }
 
     pub fn read_csv<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::read_csv_with(path, &CsvOptions::default())
+    }
+
+    pub fn read_csv_with<P: AsRef<Path>>(path: P, options: &CsvOptions) -> Result<Self, Error> {
         let file = std::fs::File::open(&path)?;
         let mut buf = BufReader::new(file);
 
         let mut s = String::new();
         buf.read_to_string(&mut s)?;
 
-        Self::read_str(s)
+        Self::read_str_with(s, options)
     }
 
     pub fn read_str(input: String) -> Result<Self, Error> {
-        let mut width = 0;
-        let mut height = 0;
-        let raw = input
-            .lines()
-            .flat_map(|line| {
-                height += 1;
-                let l = line.split(",").map(ToString::to_string).collect::<Vec<_>>();
-                width = l.len();
-                l
+        Self::read_str_with(input, &CsvOptions::default())
+    }
+
+    pub fn read_str_with(input: String, options: &CsvOptions) -> Result<Self, Error> {
+        let (headers, rows) = crate::csv::split(&input)?;
+        let width = headers.len();
+        let height = rows.len();
+        let dtypes = options.dtypes(&headers)?;
+        let data = rows
+            .iter()
+            .flat_map(|row| row.iter().zip(&dtypes))
+            .map(|(d, dtype)| match dtype {
+                Some(dtype) => Val::parse_as(d, *dtype, options),
+                None => Val::parse_with(d, options),
             })
-            .collect::<Vec<_>>();
-        let headers = raw[0..width].to_vec();
-        height -= 1;
-        let data = raw[width..].iter().map(|d| {
-            let val: Val = d.parse()?;
-            Ok::<Val, Error>(val)
-        }).collect::<Result<Vec<Val>, Error>>()?;
+            .collect::<Result<Vec<Val>, Error>>()?;
+        let headers = headers.iter().map(ToString::to_string).collect();
+
+        let mut df = Self { headers, data, width, height };
+        for (name, dtype) in &options.schema {
+            if *dtype == DType::Categorical {
+                df.insert_column(df.column(name)?.to_categorical()?)?;
+            }
+        }
+        Ok(df)
+    }
+
+    // An estimate of the bytes held by the frame, counting each shared
+    // categorical dictionary once.
+    pub fn estimated_size(&self) -> usize {
+        let mut seen = HashSet::new();
+        let headers = self.headers.iter().map(|h| size_of::<String>() + h.capacity()).sum::<usize>();
+        let heap = self.data.iter().map(|val| val.heap_size(&mut seen)).sum::<usize>();
+        size_of::<Self>() + headers + self.data.capacity() * size_of::<Val>() + heap
+    }
+
+    pub fn to_csv(&self) -> String {
+        self.to_csv_with(&CsvOptions::default())
+    }
+
+    pub fn to_csv_with(&self, options: &CsvOptions) -> String {
+        let mut out = self.headers.join(",");
+        self.data.iter().enumerate().for_each(|(i, val)| {
+            out.push(if i % self.width == 0 { '\n' } else { ',' });
+            crate::csv::write_cell(val, options, &mut out);
+        });
+        out.push('\n');
+        out
+    }
+
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        self.write_csv_with(path, &CsvOptions::default())
+    }
 
-        Ok(Self { headers, data, width, height })
+    pub fn write_csv_with<P: AsRef<Path>>(&self, path: P, options: &CsvOptions) -> Result<(), Error> {
+        std::fs::write(path, self.to_csv_with(options))?;
+        Ok(())
     }
 
     pub fn col(&self, header: &str) -> Option<Vec<&Val>> {
-        let Some(header) = self.headers.iter().position(|h| h == header) else { return None };
+        let header = self.headers.iter().position(|h| h == header)?;
         Some(self.data.iter().enumerate().filter_map(|(i, d)| {
             if i % self.width == header {
                 Some(d)
@@ -109,6 +107,63 @@ impl DataFrame {
         }).collect())
     }
 
+    pub fn column(&self, header: &str) -> Result<Column, Error> {
+        let col = self.col(header).ok_or_else(|| Error::HeaderNotFound(header.to_string()))?;
+        Ok(Column::new(header, col.into_iter().cloned().collect()))
+    }
+
+    // Adds `column` as the last column, or replaces the column with the same
+    // name. An empty DataFrame takes the height of the column.
+    pub fn insert_column(&mut self, column: Column) -> Result<(), Error> {
+        if self.width > 0 && column.len() != self.height {
+            return Err(Error::LengthMismatch { expected: self.height, found: column.len() });
+        }
+
+        if let Some(pos) = self.headers.iter().position(|h| h == column.name()) {
+            column.into_values().into_iter().enumerate().for_each(|(row, val)| {
+                self.data[row * self.width + pos] = val;
+            });
+            return Ok(());
+        }
+
+        let mut values = column.iter().cloned();
+        let mut data = Vec::with_capacity(self.data.len() + column.len());
+        for row in 0..column.len() {
+            data.extend_from_slice(&self.data[row * self.width..(row + 1) * self.width]);
+            data.extend(values.next());
+        }
+
+        self.headers.push(column.name().to_string());
+        self.data = data;
+        self.width += 1;
+        self.height = column.len();
+        Ok(())
+    }
+
+    // keeps the rows where `mask` is true, null counts as false
+    pub fn filter(&self, mask: &Column) -> Result<DataFrame, Error> {
+        if mask.len() != self.height {
+            return Err(Error::LengthMismatch { expected: self.height, found: mask.len() });
+        }
+
+        let mut data = Vec::new();
+        let mut height = 0;
+        for (row, val) in mask.iter().enumerate() {
+            if val.is_null() || !bool::try_from(val)? {
+                continue;
+            }
+            data.extend_from_slice(&self.data[row * self.width..(row + 1) * self.width]);
+            height += 1;
+        }
+
+        Ok(DataFrame {
+            headers: self.headers.clone(),
+            data,
+            width: self.width,
+            height,
+        })
+    }
+
     pub fn row(&self, idx: usize) -> Option<HashMap<&str, &Val>> {
         if idx >= self.height {
             return None;
@@ -143,6 +198,7 @@ impl DataFrame {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::BytesEncoding;
 
     fn df() -> DataFrame {
         let csv = "name,nationality,xg,goals
@@ -154,6 +210,148 @@ M. Balotelli,Italy,8.88,888
         DataFrame::read_str(csv.to_string()).unwrap()
     }
 
+    #[test]
+    fn csv_roundtrip() -> Result<(), Error> {
+        let df = df();
+        let csv = df.to_csv();
+
+        assert!(csv.starts_with("name,nationality,xg,goals\nLionel Messi,Argentine,66.66,66\n"));
+        assert_eq!(DataFrame::read_str(csv)?.to_csv(), df.to_csv());
+
+        Ok(())
+    }
+
+    #[test]
+    fn csv_roundtrip_quoted() -> Result<(), Error> {
+        let csv = "name,nickname\n\"Nunez, Darwin\",\"El \"\"Pistolero\"\"\"\n\"Lionel\nMessi\",Leo\n";
+        let df = DataFrame::read_str(csv.to_string())?;
+
+        assert_eq!((df.width, df.height), (2, 2));
+        assert_eq!(df.data[0], Val::from("Nunez, Darwin"));
+        assert_eq!(df.data[1], Val::from("El \"Pistolero\""));
+        assert_eq!(df.data[2], Val::from("Lionel\nMessi"));
+        assert_eq!(df.to_csv(), csv);
+        assert_eq!(DataFrame::read_str(df.to_csv())?.to_csv(), csv);
+
+        Ok(())
+    }
+
+    #[test]
+    fn insert_column() -> Result<(), Error> {
+        let mut df = df();
+        let ratio = (df.column("goals")? / df.column("xg")?)?.rename("goals_per_xg");
+        df.insert_column(ratio)?;
+
+        assert_eq!(df.headers().last().map(String::as_str), Some("goals_per_xg"));
+        assert!(df.row(3).is_some_and(|row| matches!(row.get("goals_per_xg"), Some(Val::Float64(r)) if (r - 100.0).abs() < 1e-9)));
+        assert_eq!(df.row(3).and_then(|row| row.get("name").copied().cloned()), Some(Val::String("M. Balotelli".into())));
+
+        df.insert_column((df.column("goals")? * Val::Int64(2))?)?;
+        assert_eq!(df.col("goals").map(|col| col[0].clone()), Some(Val::Int64(132)));
+        assert_eq!(df.headers().len(), 5);
+
+        let short = Column::new("short", vec![Val::Int64(1)]);
+        assert!(matches!(df.insert_column(short), Err(Error::LengthMismatch { expected: 4, found: 1 })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn filter() -> Result<(), Error> {
+        let df = df();
+        let mask = (df.column("goals")?.mask(|val| val > &Val::Int64(60)) & df.column("xg")?.mask(|val| val > &Val::Int64(10)))?;
+        let filtered = df.filter(&mask)?;
+
+        assert_eq!(filtered.col("name"), Some(vec![&Val::String("Lionel Messi".into()), &Val::String("Darwin Nunez".into())]));
+        assert!(matches!(df.filter(&df.column("goals")?), Err(Error::IncompatibleConversion { .. })));
+
+        let csv = "name,active\nMessi,yes\nRonaldo,NO\nNunez,\n";
+        let options = CsvOptions {
+            true_values: vec!["yes".to_string()],
+            false_values: vec!["no".to_string()],
+            ..CsvOptions::default()
+        };
+        let df = DataFrame::read_str_with(csv.to_string(), &options)?;
+        assert_eq!(df.filter(&df.column("active")?)?.col("name"), Some(vec![&Val::String("Messi".into())]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_temporal() -> Result<(), Error> {
+        let csv = "match,date,kickoff\nLIV-MCI,2024-05-12,20:00\nARS-CHE,12/05/2024,2024-05-12T17:30:00Z\n";
+        let df = DataFrame::read_str(csv.to_string())?;
+        assert_eq!(df.col("date"), Some(vec![&Val::Date("2024-05-12".parse()?), &Val::String("12/05/2024".into())]));
+        assert_eq!(df.col("kickoff").map(|col| col[0].dtype()), Some(crate::DType::Time));
+        assert_eq!(df.column("kickoff")?.hour()?.values(), &[Val::Uint8(20), Val::Uint8(17)]);
+
+        let options = CsvOptions { date_formats: vec!["%d/%m/%Y".to_string()], ..CsvOptions::default() };
+        let df = DataFrame::read_str_with(csv.to_string(), &options)?;
+        assert_eq!(df.col("date"), Some(vec![&Val::String("2024-05-12".into()), &Val::Date("2024-05-12".parse()?)]));
+        assert!(df.to_csv().contains("2024-05-12T17:30:00+00:00"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn schema() -> Result<(), Error> {
+        let csv = "item,price,qty\nboots,19.99,3\nshirt,5.5,\n";
+        let options = CsvOptions {
+            schema: vec![("price".to_string(), DType::Decimal { precision: 10, scale: 2 }), ("qty".to_string(), DType::Uint8)],
+            ..CsvOptions::default()
+        };
+        let df = DataFrame::read_str_with(csv.to_string(), &options)?;
+
+        assert_eq!(df.col("price").map(|col| col[1].to_string()), Some("5.50".to_string()));
+        assert_eq!(df.col("qty"), Some(vec![&Val::Uint8(3), &Val::Null]));
+        assert_eq!(DataFrame::read_str(csv.to_string())?.col("price").map(|col| col[0].dtype()), Some(DType::Float64));
+
+        let total = (df.column("price")? * df.column("qty")?)?;
+        assert_eq!(total.values()[0].to_string(), "59.97");
+        assert!(df.to_csv().contains("boots,19.99,3"));
+
+        let options = CsvOptions { schema: vec![("cost".to_string(), DType::Float64)], ..CsvOptions::default() };
+        assert!(matches!(DataFrame::read_str_with(csv.to_string(), &options), Err(Error::HeaderNotFound(_))));
+
+        let options = CsvOptions { schema: vec![("item".to_string(), DType::Categorical)], ..CsvOptions::default() };
+        let df = DataFrame::read_str_with(csv.to_string(), &options)?;
+        assert_eq!(df.col("item").map(|col| col[0].dtype()), Some(DType::Categorical));
+        assert!(df.to_csv().starts_with("item,price,qty\nboots,19.99,3\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn bytes_schema() -> Result<(), Error> {
+        let csv = "name,photo\nMessi,TWVzc2k=\nNunez,\n";
+        let options = CsvOptions {
+            schema: vec![("photo".to_string(), DType::Bytes)],
+            bytes_encoding: BytesEncoding::Base64,
+            ..CsvOptions::default()
+        };
+        let df = DataFrame::read_str_with(csv.to_string(), &options)?;
+
+        assert_eq!(df.col("photo"), Some(vec![&Val::Bytes(b"Messi".to_vec()), &Val::Null]));
+        assert_eq!(df.to_csv_with(&options), csv);
+        assert!(df.to_csv().contains("Messi,4d65737369\n"));
+        assert!(matches!(DataFrame::read_str_with("photo\n!!\n".to_string(), &options), Err(Error::ValParseError(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn estimated_size() -> Result<(), Error> {
+        let csv = "nationality\n".to_string() + &"Argentina\nPortugal\n".repeat(500);
+        let mut df = DataFrame::read_str(csv)?;
+        let strings = df.estimated_size();
+        df.insert_column(df.column("nationality")?.to_categorical()?)?;
+
+        assert!(df.estimated_size() < strings);
+        assert!(df.estimated_size() >= 1000 * size_of::<Val>());
+
+        Ok(())
+    }
+
     #[test]
     fn col() {
         let df = df();
@@ -227,7 +425,7 @@ M. Balotelli,Italy,8.88,888
         })?;
 
         headers.iter().for_each(|header| {
-            df.col(header).map(|col| {
+            if let Some(col) = df.col(header) {
                 col.iter().for_each(|val| {
                     match val {
                         Val::String(s) => assert!(s.contains("modify")),
@@ -236,7 +434,7 @@ M. Balotelli,Italy,8.88,888
                         _ => ()
                     }
                 });
-            });
+            }
         });
 
         Ok(())
@@ -0,0 +1,455 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use crate::{DType, Error};
+
+// The most digits an `i128` mantissa always holds.
+pub const MAX_PRECISION: u8 = 38;
+
+// The scale of a quotient when neither side asks for a finer one.
+const DIV_SCALE: u8 = 6;
+
+// How digits dropped when lowering the scale of a decimal are rounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rounding {
+    // to the nearest, ties to the even neighbour
+    #[default]
+    HalfEven,
+    // to the nearest, ties away from zero
+    HalfUp,
+    // towards zero
+    Down,
+    // away from zero
+    Up,
+    // towards negative infinity
+    Floor,
+    // towards positive infinity
+    Ceiling,
+}
+
+// A fixed-point number worth `mantissa / 10^scale`, holding at most
+// `precision` digits. Decimals compare and hash by value, so `1.5 == 1.50`.
+#[derive(Clone, Copy)]
+pub struct Decimal {
+    mantissa: i128,
+    precision: u8,
+    scale: u8,
+}
+
+fn pow10(exp: u8) -> i128 {
+    10i128.pow(exp as u32)
+}
+
+fn digits(n: i128) -> u8 {
+    n.unsigned_abs().checked_ilog10().map_or(1, |d| d as u8 + 1)
+}
+
+// `n / d` rounded to an integer
+fn div_round(n: i128, d: i128, rounding: Rounding) -> i128 {
+    let (q, r) = (n / d, n % d);
+    if r == 0 {
+        return q;
+    }
+    let negative = (n < 0) != (d < 0);
+    let (r, d) = (r.unsigned_abs(), d.unsigned_abs());
+    let away = match rounding {
+        Rounding::Down => false,
+        Rounding::Up => true,
+        Rounding::Floor => negative,
+        Rounding::Ceiling => !negative,
+        Rounding::HalfUp => r >= d - r,
+        Rounding::HalfEven => r > d - r || (r == d - r && q % 2 != 0),
+    };
+    match (away, negative) {
+        (false, _) => q,
+        (true, true) => q - 1,
+        (true, false) => q + 1,
+    }
+}
+
+impl Decimal {
+    pub fn new(mantissa: i128, precision: u8, scale: u8) -> Result<Self, Error> {
+        let dtype = DType::Decimal { precision, scale };
+        if precision == 0 || precision > MAX_PRECISION || scale > precision {
+            return Err(Error::InvalidDataType(dtype.to_string()));
+        }
+        if digits(mantissa) > precision {
+            return Err(Error::Overflow(format!("{} digits overflow {}", digits(mantissa), dtype)));
+        }
+        Ok(Self { mantissa, precision, scale })
+    }
+
+    pub fn from_int(n: i128) -> Result<Self, Error> {
+        Self::new(n, digits(n), 0)
+    }
+
+    // Parses `input` exactly into the given precision and scale, failing
+    // rather than dropping digits.
+    pub fn parse_with(input: &str, precision: u8, scale: u8) -> Result<Self, Error> {
+        let parsed = input.parse::<Decimal>()?;
+        let rescaled = parsed.round(scale, Rounding::Down)?;
+        if rescaled != parsed {
+            return Err(Error::InexactConversion {
+                value: input.to_string(),
+                to: DType::Decimal { precision, scale },
+            });
+        }
+        Self::new(rescaled.mantissa, precision, scale)
+    }
+
+    pub fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    pub fn scale(&self) -> u8 {
+        self.scale
+    }
+
+    pub fn dtype(&self) -> DType {
+        DType::Decimal { precision: self.precision, scale: self.scale }
+    }
+
+    // the value as an integer, if it has no fractional part
+    pub fn to_i128(&self) -> Option<i128> {
+        let unit = pow10(self.scale);
+        (self.mantissa % unit == 0).then_some(self.mantissa / unit)
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    // Changes the scale, rounding away digits when it gets lower. The
+    // precision follows the scale, keeping the same integral digits.
+    pub fn round(&self, scale: u8, rounding: Rounding) -> Result<Self, Error> {
+        if scale > MAX_PRECISION {
+            return Err(self.overflow(scale));
+        }
+        let mantissa = match scale.cmp(&self.scale) {
+            Ordering::Equal => self.mantissa,
+            Ordering::Less => div_round(self.mantissa, pow10(self.scale - scale), rounding),
+            Ordering::Greater => self
+                .mantissa
+                .checked_mul(pow10(scale - self.scale))
+                .ok_or_else(|| self.overflow(scale))?,
+        };
+        let integral = self.precision - self.scale;
+        Self::with_digits(Some(mantissa), integral + scale, scale, || self.overflow(scale))
+    }
+
+    fn overflow(&self, scale: u8) -> Error {
+        Error::Overflow(format!("{self} at scale {scale} overflows decimal({MAX_PRECISION}, {scale})"))
+    }
+
+    // both mantissas at the larger of the two scales
+    fn align(&self, other: &Decimal) -> Result<(i128, i128, u8), Error> {
+        let scale = self.scale.max(other.scale);
+        let lhs = self.mantissa.checked_mul(pow10(scale - self.scale));
+        let rhs = other.mantissa.checked_mul(pow10(scale - other.scale));
+        match (lhs, rhs) {
+            (Some(lhs), Some(rhs)) => Ok((lhs, rhs, scale)),
+            _ => Err(Error::Overflow(format!("{self} and {other} at scale {scale}"))),
+        }
+    }
+
+    fn with_digits(mantissa: Option<i128>, precision: u8, scale: u8, overflow: impl Fn() -> Error) -> Result<Self, Error> {
+        let mantissa = mantissa.ok_or_else(&overflow)?;
+        // at least the digits held, and at most what the overflow check allows
+        let precision = precision.min(MAX_PRECISION).max(digits(mantissa)).max(scale).max(1);
+        Self::new(mantissa, precision, scale).map_err(|_| overflow())
+    }
+
+    pub fn checked_add(&self, rhs: &Decimal) -> Result<Self, Error> {
+        let (lhs, rhs_m, scale) = self.align(rhs)?;
+        let integral = (self.precision - self.scale).max(rhs.precision - rhs.scale);
+        let overflow = || Error::Overflow(format!("{self} + {rhs}"));
+        Self::with_digits(lhs.checked_add(rhs_m), integral + scale + 1, scale, overflow)
+    }
+
+    pub fn checked_sub(&self, rhs: &Decimal) -> Result<Self, Error> {
+        let (lhs, rhs_m, scale) = self.align(rhs)?;
+        let integral = (self.precision - self.scale).max(rhs.precision - rhs.scale);
+        let overflow = || Error::Overflow(format!("{self} - {rhs}"));
+        Self::with_digits(lhs.checked_sub(rhs_m), integral + scale + 1, scale, overflow)
+    }
+
+    pub fn checked_mul(&self, rhs: &Decimal) -> Result<Self, Error> {
+        let overflow = || Error::Overflow(format!("{self} * {rhs}"));
+        let scale = self.scale + rhs.scale;
+        if scale > MAX_PRECISION {
+            return Err(overflow());
+        }
+        Self::with_digits(self.mantissa.checked_mul(rhs.mantissa), self.precision + rhs.precision, scale, overflow)
+    }
+
+    // `self / rhs` at the given scale
+    pub fn checked_div(&self, rhs: &Decimal, scale: u8, rounding: Rounding) -> Result<Self, Error> {
+        if rhs.mantissa == 0 {
+            return Err(Error::DivisionByZero);
+        }
+        let overflow = || Error::Overflow(format!("{self} / {rhs} at scale {scale}"));
+        if scale > MAX_PRECISION {
+            return Err(overflow());
+        }
+        // self.m / 10^s1 / (rhs.m / 10^s2) * 10^scale
+        let shift = (scale + rhs.scale).checked_sub(self.scale);
+        let (n, d) = match shift {
+            Some(shift) if shift <= MAX_PRECISION => (self.mantissa.checked_mul(pow10(shift)), rhs.mantissa),
+            Some(_) => (None, rhs.mantissa),
+            None => (Some(self.mantissa), rhs.mantissa.checked_mul(pow10(self.scale - scale - rhs.scale)).ok_or_else(overflow)?),
+        };
+        let quotient = n.map(|n| div_round(n, d, rounding));
+        Self::with_digits(quotient, MAX_PRECISION, scale, overflow)
+    }
+
+    pub fn checked_rem(&self, rhs: &Decimal) -> Result<Self, Error> {
+        if rhs.mantissa == 0 {
+            return Err(Error::DivisionByZero);
+        }
+        let (lhs, rhs_m, scale) = self.align(rhs)?;
+        let overflow = || Error::Overflow(format!("{self} % {rhs}"));
+        Self::with_digits(lhs.checked_rem(rhs_m), self.precision.max(rhs.precision), scale, overflow)
+    }
+
+    // the quotient of the `/` operator, at a scale of at least six digits
+    pub(crate) fn div_default(&self, rhs: &Decimal) -> Result<Self, Error> {
+        let scale = self.scale.max(rhs.scale).max(DIV_SCALE);
+        self.checked_div(rhs, scale, Rounding::default())
+    }
+
+    pub fn checked_neg(&self) -> Option<Self> {
+        self.mantissa.checked_neg().map(|mantissa| Self { mantissa, ..*self })
+    }
+
+    // compares with an integer, which may not fit the precision
+    pub(crate) fn cmp_int(&self, n: i128) -> Ordering {
+        let unit = pow10(self.scale);
+        (self.mantissa / unit)
+            .cmp(&n)
+            .then((self.mantissa % unit).cmp(&0))
+    }
+
+    // Compares exactly with the value of a float, NaN being greater than
+    // every number.
+    pub(crate) fn cmp_f64(&self, f: f64) -> Ordering {
+        // the integral part of a decimal is below 10^38 < 2^127
+        if f.is_nan() || f.trunc() >= 2f64.powi(127) {
+            return Ordering::Less;
+        } else if f.trunc() <= -(2f64.powi(127)) {
+            return Ordering::Greater;
+        }
+        let unit = pow10(self.scale);
+        let (int, frac) = (self.mantissa / unit, self.mantissa % unit);
+        int.cmp(&(f.trunc() as i128)).then_with(|| {
+            let sign = if f.fract() > 0.0 { 1 } else if f.fract() < 0.0 { -1 } else { 0 };
+            match frac.signum().cmp(&sign) {
+                Ordering::Equal if sign == 0 => Ordering::Equal,
+                Ordering::Equal => {
+                    // `f.fract()` is `mantissa * 2^-shift`, compared with
+                    // `frac / 10^scale` as `frac * 2^shift` to `mantissa * 10^scale`
+                    let bits = f.fract().abs().to_bits();
+                    let exp = (bits >> 52) as u32;
+                    let mantissa = if exp == 0 { bits } else { bits & ((1 << 52) - 1) | 1 << 52 };
+                    let shift = 1075 - exp.max(1);
+                    let ord = cmp_big(&big(frac.unsigned_abs(), shift, 0), &big(mantissa as u128, 0, self.scale));
+                    if sign < 0 { ord.reverse() } else { ord }
+                },
+                ord => ord,
+            }
+        })
+    }
+
+    // the mantissa and scale without trailing zeros
+    fn normalized(&self) -> (i128, u8) {
+        let (mut mantissa, mut scale) = (self.mantissa, self.scale);
+        while scale > 0 && mantissa % 10 == 0 {
+            mantissa /= 10;
+            scale -= 1;
+        }
+        (mantissa, scale)
+    }
+}
+
+// `n * 2^shift * 10^exp` as little-endian 32-bit limbs, for comparisons too
+// wide for an `i128`
+fn big(n: u128, shift: u32, exp: u8) -> Vec<u32> {
+    let mut limbs = (0..4).map(|i| (n >> (32 * i)) as u32).collect::<Vec<_>>();
+    for _ in 0..exp {
+        let mut carry = 0;
+        for limb in &mut limbs {
+            let product = *limb as u64 * 10 + carry;
+            *limb = product as u32;
+            carry = product >> 32;
+        }
+        limbs.push(carry as u32);
+    }
+    let (words, bits) = ((shift / 32) as usize, shift % 32);
+    let mut shifted = vec![0; words];
+    let mut carry = 0;
+    for limb in limbs {
+        shifted.push(limb << bits | carry);
+        carry = if bits == 0 { 0 } else { limb >> (32 - bits) };
+    }
+    shifted.push(carry);
+    while shifted.last() == Some(&0) {
+        shifted.pop();
+    }
+    shifted
+}
+
+fn cmp_big(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Integer parts first, then the fractional parts brought to a common scale,
+// which always fits as both are below 10^38.
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (u1, u2) = (pow10(self.scale), pow10(other.scale));
+        let scale = self.scale.max(other.scale);
+        let f1 = self.mantissa % u1 * pow10(scale - self.scale);
+        let f2 = other.mantissa % u2 * pow10(scale - other.scale);
+        (self.mantissa / u1).cmp(&(other.mantissa / u2)).then(f1.cmp(&f2))
+    }
+}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalized().hash(state)
+    }
+}
+
+// Reads `[+-]digits[.digits]`, keeping every digit: the precision and scale
+// are the ones of the text, so `19.990` is a decimal(5, 3).
+impl FromStr for Decimal {
+    type Err = Error;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::ValParseError(input.to_string());
+        let (negative, unsigned) = match input.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, input.strip_prefix('+').unwrap_or(input)),
+        };
+        let (int, frac) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        if int.is_empty() && frac.is_empty() {
+            return Err(invalid());
+        }
+        if !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let int = int.trim_start_matches('0');
+        let precision = (int.len() + frac.len()).max(1);
+        if precision > MAX_PRECISION as usize {
+            return Err(Error::Overflow(format!("{input} has more than {MAX_PRECISION} digits")));
+        }
+        let mantissa = format!("0{int}{frac}").parse::<i128>().map_err(|_| invalid())?;
+        let mantissa = if negative { -mantissa } else { mantissa };
+        Self::new(mantissa, precision as u8, frac.len() as u8)
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = format!("{:0width$}", self.mantissa.unsigned_abs(), width = self.scale as usize + 1);
+        let (int, frac) = digits.split_at(digits.len() - self.scale as usize);
+        if self.mantissa < 0 {
+            f.write_str("-")?;
+        }
+        f.write_str(int)?;
+        if !frac.is_empty() {
+            write!(f, ".{frac}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Decimal({self}, {})", self.dtype())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(input: &str) -> Decimal {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() -> Result<(), Error> {
+        assert_eq!(dec("19.99").to_string(), "19.99");
+        assert_eq!(dec("-0.05").to_string(), "-0.05");
+        assert_eq!(dec(".5").to_string(), "0.5");
+        assert_eq!(dec("19.990").dtype(), DType::Decimal { precision: 5, scale: 3 });
+        assert_eq!(Decimal::parse_with("19.9", 10, 2)?.to_string(), "19.90");
+        assert!(matches!(Decimal::parse_with("19.999", 10, 2), Err(Error::InexactConversion { .. })));
+        assert!(matches!(Decimal::parse_with("12345.6", 4, 1), Err(Error::Overflow(_))));
+        assert!("1e5".parse::<Decimal>().is_err());
+        assert!("-".parse::<Decimal>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn exact_arithmetic() -> Result<(), Error> {
+        let sum = (0..10).try_fold(dec("0.00"), |acc, _| acc.checked_add(&dec("0.10")))?;
+
+        assert_eq!(sum, dec("1"));
+        assert_eq!(dec("19.99").checked_mul(&dec("3"))?.to_string(), "59.97");
+        assert_eq!(dec("10").div_default(&dec("3"))?.to_string(), "3.333333");
+        assert_eq!(dec("7.5").checked_rem(&dec("2"))?.to_string(), "1.5");
+        assert!(matches!(dec("1").checked_div(&dec("0.0"), 2, Rounding::Down), Err(Error::DivisionByZero)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn rounding_modes() -> Result<(), Error> {
+        let round = |input: &str, rounding| dec(input).round(0, rounding).map(|d| d.to_string());
+
+        assert_eq!(round("2.5", Rounding::HalfEven)?, "2");
+        assert_eq!(round("3.5", Rounding::HalfEven)?, "4");
+        assert_eq!(round("-2.5", Rounding::HalfUp)?, "-3");
+        assert_eq!(round("-2.1", Rounding::Floor)?, "-3");
+        assert_eq!(round("-2.9", Rounding::Ceiling)?, "-2");
+        assert_eq!(round("2.1", Rounding::Up)?, "3");
+        assert_eq!(round("2.9", Rounding::Down)?, "2");
+        assert_eq!(dec("1.5").round(3, Rounding::Down)?.to_string(), "1.500");
+        assert_eq!(dec("9.9").round(0, Rounding::HalfUp)?.dtype(), DType::Decimal { precision: 2, scale: 0 });
+
+        Ok(())
+    }
+
+    #[test]
+    fn compare_by_value() {
+        assert_eq!(dec("1.5"), dec("1.50"));
+        assert!(dec("-1.5") < dec("-1.2"));
+        assert!(dec("0.5") > dec("-0.5"));
+        assert_eq!(dec("3.00").cmp_int(3), Ordering::Equal);
+        assert_eq!(dec("-0.5").cmp_int(0), Ordering::Less);
+    }
+}
@@ -0,0 +1,95 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DType {
+    Null,
+
+    Bool,
+
+    String,
+    Categorical,
+
+    Isize,
+    Usize,
+
+    Int128,
+    UInt128,
+
+    Int64,
+    Uint64,
+
+    Int32,
+    Uint32,
+
+    Int16,
+    Uint16,
+
+    Int8,
+    Uint8,
+
+    Float64,
+    Float32,
+
+    Decimal { precision: u8, scale: u8 },
+
+    Date,
+    Time,
+    Datetime,
+    Duration,
+
+    List,
+    Struct,
+
+    Bytes,
+}
+
+impl std::fmt::Display for DType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            DType::Decimal { precision, scale } => return write!(f, "decimal({precision}, {scale})"),
+            DType::Null => "null",
+            DType::Bool => "bool",
+            DType::String => "str",
+            DType::Categorical => "cat",
+            DType::Isize => "isize",
+            DType::Usize => "usize",
+            DType::Int128 => "i128",
+            DType::UInt128 => "u128",
+            DType::Int64 => "i64",
+            DType::Uint64 => "u64",
+            DType::Int32 => "i32",
+            DType::Uint32 => "u32",
+            DType::Int16 => "i16",
+            DType::Uint16 => "u16",
+            DType::Int8 => "i8",
+            DType::Uint8 => "u8",
+            DType::Float64 => "f64",
+            DType::Float32 => "f32",
+            DType::Date => "date",
+            DType::Time => "time",
+            DType::Datetime => "datetime",
+            DType::Duration => "duration",
+            DType::List => "list",
+            DType::Struct => "struct",
+            DType::Bytes => "bytes",
+        };
+
+        f.write_str(name)
+    }
+}
+
+impl DType {
+    pub fn is_numeric(&self) -> bool {
+        !matches!(self, DType::Null | DType::Bool | DType::String | DType::Categorical | DType::List | DType::Struct | DType::Bytes) && !self.is_temporal()
+    }
+
+    pub fn is_temporal(&self) -> bool {
+        matches!(self, DType::Date | DType::Time | DType::Datetime | DType::Duration)
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, DType::Float64 | DType::Float32)
+    }
+
+    pub fn is_decimal(&self) -> bool {
+        matches!(self, DType::Decimal { .. })
+    }
+}
@@ -1,5 +1,7 @@
 use std::io;
 
+use crate::DType;
+
 #[derive(Debug)]
 pub enum Error {
     Io(io::ErrorKind),
@@ -7,14 +9,43 @@ pub enum Error {
     ValParseError(String),
     InvalidDataType(String),
     IncompatibleStruct {
-        struct_fields: usize,
-        csv_columns: usize,
-        incompatible: String,
+        missing: Vec<String>,
+        extra: Vec<String>,
+    },
+    UnknownVariant {
+        value: String,
+        allowed: Vec<String>,
+    },
+    Overflow(String),
+    DivisionByZero,
+    UnsupportedOperation(String),
+    LengthMismatch {
+        expected: usize,
+        found: usize,
+    },
+    RowLength {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    IncompatibleConversion {
+        from: DType,
+        to: DType,
+    },
+    InexactConversion {
+        value: String,
+        to: DType,
+    },
+    IndexOutOfBounds {
+        index: usize,
+        len: usize,
     },
-    ValToString,
-    ValToFloat64,
-    ValToInt64,
-    ValToUsize,
+    FractionOutOfRange(f64),
+    EmptyWindow,
+    DuplicateEntry(String),
+    DuplicateHeader(String),
+    MalformedQuote(String),
+    BorrowedEscape(String),
     Other(String),
 }
 
@@ -26,15 +57,53 @@ impl std::fmt::Display for Error {
             Self::ValParseError(p) => format!("Unable to parse {p} into Val"),
             Self::InvalidDataType(s) => s.to_string(),
             Self::IncompatibleStruct {
-                struct_fields,
-                csv_columns,
-                incompatible
-            } => format!("Struct has {struct_fields} fields, while csv data only has {csv_columns} columns. {incompatible} is incompatible"),
+                missing,
+                extra
+            } => {
+                let mut text = String::from("Struct is incompatible with csv data.");
+                if !missing.is_empty() {
+                    text += &format!(" Missing columns: {}.", missing.join(", "));
+                }
+                if !extra.is_empty() {
+                    text += &format!(" Extra columns: {}.", extra.join(", "));
+                }
+                text
+            },
+            Self::UnknownVariant {
+                value,
+                allowed
+            } => format!("Unknown variant {value:?}, expected one of {}", allowed.join(", ")),
+            Self::Overflow(op) => format!("Arithmetic overflow: {op}"),
+            Self::DivisionByZero => "Division by zero".to_string(),
+            Self::UnsupportedOperation(op) => format!("Unsupported operation: {op}"),
+            Self::LengthMismatch {
+                expected,
+                found
+            } => format!("Expected a column of length {expected}, found {found}"),
+            Self::RowLength {
+                row,
+                expected,
+                found
+            } => format!("Row {row} has {found} columns, while the header has {expected}"),
+            Self::IncompatibleConversion {
+                from,
+                to
+            } => format!("Unable to convert {from} into {to}"),
+            Self::InexactConversion {
+                value,
+                to
+            } => format!("{value} can't be represented exactly as {to}"),
+            Self::IndexOutOfBounds {
+                index,
+                len
+            } => format!("Index {index} is out of bounds for length {len}"),
+            Self::FractionOutOfRange(frac) => format!("{frac} is not between 0 and 1"),
+            Self::EmptyWindow => "A rolling window holds at least one row".to_string(),
+            Self::DuplicateEntry(entry) => format!("Duplicate entry for {entry}, an aggregation is needed"),
+            Self::DuplicateHeader(header) => format!("Header {header} is not unique"),
+            Self::MalformedQuote(cell) => format!("Malformed quoted cell {cell}"),
+            Self::BorrowedEscape(cell) => format!("Cell {cell:?} had its quotes unescaped and can't be borrowed"),
             Self::Other(s) => s.to_string(),
-            Self::ValToString
-            | Self::ValToFloat64
-            | Self::ValToInt64
-            | Self::ValToUsize => "Incompatible type conversion".to_string()
         };
 
         f.write_str(text.as_str())
@@ -0,0 +1,192 @@
+use crate::{
+    table::{FormatOptions, GridColumn},
+    unicode::width,
+    DataFrame,
+};
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('|', "\\|").replace("\r\n", "<br>").replace(['\n', '\r'], "<br>")
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn escape_latex(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                out.push('\\');
+                out.push(c);
+            },
+            '~' => out.push_str("\\textasciitilde{}"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            '\\' => out.push_str("\\textbackslash{}"),
+            '<' => out.push_str("\\textless{}"),
+            '>' => out.push_str("\\textgreater{}"),
+            '|' => out.push_str("\\textbar{}"),
+            '\n' => out.push(' '),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+impl DataFrame {
+    // Every cell escaped with `escape`, one line per row.
+    fn escaped_grid<F: Fn(&str) -> String>(&self, options: &FormatOptions, escape: F) -> (Vec<GridColumn>, Vec<String>, Vec<Vec<String>>) {
+        let columns = self.grid_columns(options);
+        let headers = columns.iter().map(|c| escape(&c.header)).collect();
+        let rows = (0..self.height)
+            .map(|row| self.grid_row(row, options).iter().map(|cell| escape(cell)).collect())
+            .collect();
+        (columns, headers, rows)
+    }
+
+    pub fn to_markdown(&self) -> String {
+        self.to_markdown_with(&FormatOptions::default())
+    }
+
+    // A github flavoured markdown table, numbers aligned to the right.
+    pub fn to_markdown_with(&self, options: &FormatOptions) -> String {
+        let (columns, headers, rows) = self.escaped_grid(options, escape_markdown);
+        let mut widths = headers.iter().map(|header| width(header).max(3)).collect::<Vec<_>>();
+        for row in &rows {
+            row.iter().zip(&mut widths).for_each(|(cell, w)| *w = (*w).max(width(cell)));
+        }
+
+        let line = |cells: &[String]| {
+            let cells = cells.iter().zip(&widths).zip(&columns).map(|((cell, &w), column)| {
+                let pad = " ".repeat(w - width(cell));
+                if column.right { format!("{pad}{cell}") } else { format!("{cell}{pad}") }
+            });
+            format!("| {} |\n", cells.collect::<Vec<_>>().join(" | "))
+        };
+        let rule = widths.iter().zip(&columns).map(|(&w, column)| {
+            if column.right { format!("{}:", "-".repeat(w - 1)) } else { "-".repeat(w) }
+        });
+
+        let mut out = line(&headers);
+        out.push_str(&format!("| {} |\n", rule.collect::<Vec<_>>().join(" | ")));
+        rows.iter().for_each(|row| out.push_str(&line(row)));
+        out
+    }
+
+    pub fn to_html(&self) -> String {
+        self.to_html_with(&FormatOptions::default())
+    }
+
+    // A `<table>` with a head and a body, the index written as row headers.
+    pub fn to_html_with(&self, options: &FormatOptions) -> String {
+        let (columns, headers, rows) = self.escaped_grid(options, escape_html);
+        let mut out = String::from("<table");
+        if !options.html_classes.is_empty() {
+            out.push_str(&format!(" class=\"{}\"", escape_html(&options.html_classes.join(" "))));
+        }
+        out.push_str(">\n  <thead>\n    <tr>");
+        headers.iter().for_each(|header| out.push_str(&format!("<th>{header}</th>")));
+        out.push_str("</tr>\n  </thead>\n  <tbody>\n");
+        for row in &rows {
+            out.push_str("    <tr>");
+            for (i, (cell, column)) in row.iter().zip(&columns).enumerate() {
+                let tag = if options.show_index && i == 0 { "th" } else { "td" };
+                let style = if column.right { " style=\"text-align: right\"" } else { "" };
+                out.push_str(&format!("<{tag}{style}>{cell}</{tag}>"));
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("  </tbody>\n</table>\n");
+        out
+    }
+
+    pub fn to_latex(&self) -> String {
+        self.to_latex_with(&FormatOptions::default())
+    }
+
+    // A `tabular` environment, numbers aligned to the right.
+    pub fn to_latex_with(&self, options: &FormatOptions) -> String {
+        let (columns, headers, rows) = self.escaped_grid(options, escape_latex);
+        let spec = columns.iter().map(|column| if column.right { 'r' } else { 'l' }).collect::<String>();
+
+        let mut out = format!("\\begin{{tabular}}{{{spec}}}\n\\hline\n");
+        out.push_str(&format!("{} \\\\\n\\hline\n", headers.join(" & ")));
+        rows.iter().for_each(|row| out.push_str(&format!("{} \\\\\n", row.join(" & "))));
+        out.push_str("\\hline\n\\end{tabular}\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    fn df() -> Result<DataFrame, Error> {
+        DataFrame::read_str("name,xg\nMessi & co,66.666\n<Nunez|_>,\n".to_string())
+    }
+
+    #[test]
+    fn markdown() -> Result<(), Error> {
+        let expected = "\
+| name       |     xg |
+| ---------- | -----: |
+| Messi & co | 66.666 |
+| <Nunez\\|_> |   null |
+";
+        assert_eq!(df()?.to_markdown(), expected);
+
+        let options = FormatOptions { float_precision: Some(1), show_index: true, ..FormatOptions::default() };
+        assert!(df()?.to_markdown_with(&options).starts_with("|     | name       |   xg |\n| --: | ---------- | ---: |\n|   0 | Messi & co | 66.7 |\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn html() -> Result<(), Error> {
+        let options = FormatOptions { show_index: true, html_classes: vec!["table".into(), "striped".into()], ..FormatOptions::default() };
+        let expected = "\
+<table class=\"table striped\">
+  <thead>
+    <tr><th></th><th>name</th><th>xg</th></tr>
+  </thead>
+  <tbody>
+    <tr><th style=\"text-align: right\">0</th><td>Messi &amp; co</td><td style=\"text-align: right\">66.666</td></tr>
+    <tr><th style=\"text-align: right\">1</th><td>&lt;Nunez|_&gt;</td><td style=\"text-align: right\">null</td></tr>
+  </tbody>
+</table>
+";
+        assert_eq!(df()?.to_html_with(&options), expected);
+        assert!(df()?.to_html().starts_with("<table>\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn latex() -> Result<(), Error> {
+        let expected = "\
+\\begin{tabular}{lr}
+\\hline
+name & xg \\\\
+\\hline
+Messi \\& co & 66.666 \\\\
+\\textless{}Nunez\\textbar{}\\_\\textgreater{} & null \\\\
+\\hline
+\\end{tabular}
+";
+        assert_eq!(df()?.to_latex(), expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+use std::borrow::Cow;
+
+use crate::{CsvOptions, Date, Datetime, DType, Error, Time, Val};
+
+// Conversion between a single csv cell and a struct field, used by the
+// `DataFrame` derive. `'src` is the lifetime of the csv input, which lets
+// borrowed fields like `&'a str` point straight into it. A quoted cell with
+// escaped quotes comes as `Cow::Owned`, having nothing in the input to point at.
+pub trait Field<'src>: Sized {
+    const DTYPE: DType;
+
+    fn parse_field(input: Cow<'src, str>) -> Result<Self, Error>;
+
+    // Parses a cell straight into a `Val`, without being tied to `'src`.
+    fn parse_val(input: &str) -> Result<Val, Error>;
+
+    fn to_val(&self) -> Val;
+}
+
+macro_rules! impl_field {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl Field<'_> for $ty {
+                const DTYPE: DType = DType::$variant;
+
+                fn parse_field(input: Cow<'_, str>) -> Result<Self, Error> {
+                    input
+                        .parse::<$ty>()
+                        .map_err(|_| Error::ValParseError(input.to_string()))
+                }
+
+                fn parse_val(input: &str) -> Result<Val, Error> {
+                    Self::parse_field(input.into()).map(Val::$variant)
+                }
+
+                fn to_val(&self) -> Val {
+                    Val::$variant(self.clone())
+                }
+            }
+        )*
+    };
+}
+
+impl_field! {
+    String => String,
+    isize => Isize,
+    usize => Usize,
+    i128 => Int128,
+    u128 => UInt128,
+    i64 => Int64,
+    u64 => Uint64,
+    i32 => Int32,
+    u32 => Uint32,
+    i16 => Int16,
+    u16 => Uint16,
+    i8 => Int8,
+    u8 => Uint8,
+    f64 => Float64,
+    f32 => Float32,
+}
+
+// Dates and times are read in the default formats of `CsvOptions`.
+macro_rules! impl_temporal_field {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl Field<'_> for $ty {
+                const DTYPE: DType = DType::$ty;
+
+                fn parse_field(input: Cow<'_, str>) -> Result<Self, Error> {
+                    input.parse::<$ty>()
+                }
+
+                fn parse_val(input: &str) -> Result<Val, Error> {
+                    Self::parse_field(input.into()).map(Val::$ty)
+                }
+
+                fn to_val(&self) -> Val {
+                    Val::$ty(*self)
+                }
+            }
+        )*
+    };
+}
+
+impl_temporal_field!(Date, Time, Datetime);
+
+// Accepts the default tokens of `CsvOptions`.
+impl Field<'_> for bool {
+    const DTYPE: DType = DType::Bool;
+
+    fn parse_field(input: Cow<'_, str>) -> Result<Self, Error> {
+        CsvOptions::default()
+            .parse_bool(&input)
+            .ok_or_else(|| Error::ValParseError(input.to_string()))
+    }
+
+    fn parse_val(input: &str) -> Result<Val, Error> {
+        Self::parse_field(input.into()).map(Val::Bool)
+    }
+
+    fn to_val(&self) -> Val {
+        Val::Bool(*self)
+    }
+}
+
+impl<'a, 'src: 'a> Field<'src> for &'a str {
+    const DTYPE: DType = DType::String;
+
+    fn parse_field(input: Cow<'src, str>) -> Result<Self, Error> {
+        match input {
+            Cow::Borrowed(input) => Ok(input),
+            Cow::Owned(input) => Err(Error::BorrowedEscape(input)),
+        }
+    }
+
+    fn parse_val(input: &str) -> Result<Val, Error> {
+        Ok(Val::String(input.to_string()))
+    }
+
+    fn to_val(&self) -> Val {
+        Val::String(self.to_string())
+    }
+}
+
+// An empty cell is `None`.
+impl<'src, T: Field<'src>> Field<'src> for Option<T> {
+    const DTYPE: DType = T::DTYPE;
+
+    fn parse_field(input: Cow<'src, str>) -> Result<Self, Error> {
+        if input.is_empty() {
+            Ok(None)
+        } else {
+            T::parse_field(input).map(Some)
+        }
+    }
+
+    fn parse_val(input: &str) -> Result<Val, Error> {
+        if input.is_empty() {
+            Ok(Val::Null)
+        } else {
+            T::parse_val(input)
+        }
+    }
+
+    fn to_val(&self) -> Val {
+        self.as_ref().map_or(Val::Null, T::to_val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_primitives() -> Result<(), Error> {
+        assert_eq!(<u8 as Field>::parse_val("255")?, Val::Uint8(255));
+        assert!(<u8 as Field>::parse_val("256").is_err());
+        assert_eq!(<f32 as Field>::parse_field("-0.5".into())?, -0.5);
+        assert_eq!(<i64 as Field>::parse_field("-3".into())?.to_val(), Val::Int64(-3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn borrow_str() -> Result<(), Error> {
+        let input = String::from("Lionel Messi");
+        let name = <&str as Field>::parse_field(input.as_str().into())?;
+
+        assert!(std::ptr::eq(name, input.as_str()));
+        assert_eq!(name.to_val(), Val::String(input.clone()));
+        assert!(matches!(
+            <&str as Field>::parse_field(Cow::Owned("Lionel \"Leo\" Messi".to_string())),
+            Err(Error::BorrowedEscape(_))
+        ));
+        assert_eq!(<String as Field>::parse_field(Cow::Owned("\"Leo\"".to_string()))?, "\"Leo\"");
+
+        Ok(())
+    }
+
+    #[test]
+    fn optional() -> Result<(), Error> {
+        assert_eq!(<Option<u32> as Field>::parse_field("".into())?, None);
+        assert_eq!(<Option<u32> as Field>::parse_field("3".into())?, Some(3));
+        assert_eq!(<Option<&str> as Field>::parse_val("")?, Val::Null);
+        assert_eq!(None::<f64>.to_val(), Val::Null);
+
+        Ok(())
+    }
+}
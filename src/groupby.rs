@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+
+use crate::{categorical::shared_categories, reduce, DataFrame, Error, Val};
+
+// The rows of a dataframe split by the values of its key columns, groups
+// being in the order their key first appears.
+pub struct GroupBy<'a> {
+    df: &'a DataFrame,
+    by: Vec<usize>,
+    groups: Vec<Vec<usize>>,
+}
+
+impl DataFrame {
+    pub(crate) fn position(&self, header: &str) -> Result<usize, Error> {
+        self.headers
+            .iter()
+            .position(|h| h == header)
+            .ok_or_else(|| Error::HeaderNotFound(header.to_string()))
+    }
+
+    pub(crate) fn column_values(&self, col: usize) -> impl Iterator<Item = &Val> + '_ {
+        self.data.iter().skip(col).step_by(self.width.max(1)).take(self.height)
+    }
+
+    // the rows of each group of `by`, or every row when `by` is empty
+    pub(crate) fn partitions(&self, by: &[&str]) -> Result<Vec<Vec<usize>>, Error> {
+        if by.is_empty() {
+            return Ok(vec![(0..self.height).collect()]);
+        }
+        Ok(self.group_by(by)?.groups)
+    }
+
+    pub fn group_by(&self, by: &[&str]) -> Result<GroupBy<'_>, Error> {
+        let by = by.iter().map(|header| self.position(header)).collect::<Result<Vec<_>, Error>>()?;
+        let groups = group_rows(self, &by);
+        Ok(GroupBy { df: self, by, groups })
+    }
+}
+
+// Per-row codes of a column, equal values getting equal codes. A column of
+// categoricals sharing one dictionary keeps its codes, with 0 for nulls,
+// other columns have their values hashed.
+fn key_codes(df: &DataFrame, col: usize) -> Vec<usize> {
+    if shared_categories(df.column_values(col)).is_some() {
+        return df
+            .column_values(col)
+            .map(|val| match val {
+                Val::Categorical(c) => c.code() as usize + 1,
+                _ => 0,
+            })
+            .collect();
+    }
+
+    let mut seen = HashMap::new();
+    df.column_values(col)
+        .map(|val| {
+            let next = seen.len();
+            *seen.entry(val).or_insert(next)
+        })
+        .collect()
+}
+
+fn group_rows(df: &DataFrame, by: &[usize]) -> Vec<Vec<usize>> {
+    let codes = by.iter().map(|&col| key_codes(df, col)).collect::<Vec<_>>();
+    let mut groups = Vec::<Vec<usize>>::new();
+
+    if let [codes] = codes.as_slice() {
+        // codes are dense, so a single key indexes its groups directly
+        let mut index = vec![None; codes.iter().max().map_or(0, |max| max + 1)];
+        for (row, &code) in codes.iter().enumerate() {
+            let group = *index[code].get_or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(row);
+        }
+    } else {
+        let mut index = HashMap::<Vec<usize>, usize>::new();
+        for row in 0..df.height {
+            let key = codes.iter().map(|codes| codes[row]).collect();
+            let group = *index.entry(key).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(row);
+        }
+    }
+
+    groups
+}
+
+impl GroupBy<'_> {
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    // the row indices of each group
+    pub fn groups(&self) -> &[Vec<usize>] {
+        &self.groups
+    }
+
+    // One row per group holding its key columns followed by `values`.
+    fn frame(&self, name: &str, values: Vec<Val>) -> DataFrame {
+        let df = self.df;
+        let mut headers = self.by.iter().map(|&col| df.headers[col].clone()).collect::<Vec<_>>();
+        headers.push(name.to_string());
+
+        let mut data = Vec::with_capacity(headers.len() * self.groups.len());
+        for (rows, val) in self.groups.iter().zip(values) {
+            data.extend(self.by.iter().map(|&col| df.data[rows[0] * df.width + col].clone()));
+            data.push(val);
+        }
+
+        DataFrame::new(headers, data, self.by.len() + 1, self.groups.len())
+    }
+
+    pub fn count(&self) -> DataFrame {
+        let counts = self.groups.iter().map(|rows| Val::Usize(rows.len())).collect();
+        self.frame("count", counts)
+    }
+
+    // reduces the values of `column` within each group
+    pub fn agg<F: FnMut(&[&Val]) -> Result<Val, Error>>(&self, column: &str, mut f: F) -> Result<DataFrame, Error> {
+        let df = self.df;
+        let col = df.position(column)?;
+        let values = self
+            .groups
+            .iter()
+            .map(|rows| {
+                let vals = rows.iter().map(|row| &df.data[row * df.width + col]).collect::<Vec<_>>();
+                f(&vals)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(self.frame(column, values))
+    }
+
+    // the sum of the non-null values of each group, null when there are
+    // none, widened like `Column::sum`
+    pub fn sum(&self, column: &str) -> Result<DataFrame, Error> {
+        self.agg(column, reduce::sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CsvOptions, DType};
+
+    fn df() -> DataFrame {
+        let csv = "name,nationality,club,goals
+Lionel Messi,Argentina,Barcelona,66
+C. Ronaldo,Portugal,Madrid,3
+Darwin Nunez,Uruguay,Liverpool,
+Paulo Dybala,Argentina,Roma,20
+M. Balotelli,Italy,Liverpool,888
+";
+        DataFrame::read_str(csv.to_string()).unwrap()
+    }
+
+    #[test]
+    fn group_strings_and_categoricals() -> Result<(), Error> {
+        let mut df = df();
+        let counts = df.group_by(&["nationality"])?.count();
+
+        assert_eq!(counts.headers(), &["nationality", "count"]);
+        assert_eq!(counts.col("count"), Some(vec![&Val::Usize(2), &Val::Usize(1), &Val::Usize(1), &Val::Usize(1)]));
+
+        df.insert_column(df.column("nationality")?.to_categorical()?)?;
+        let sums = df.group_by(&["nationality"])?.sum("goals")?;
+        assert_eq!(sums.col("nationality"), counts.col("nationality"));
+        assert_eq!(sums.col("goals"), Some(vec![&Val::Int64(86), &Val::Int64(3), &Val::Null, &Val::Int64(888)]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn sum_widens() -> Result<(), Error> {
+        let options = CsvOptions { schema: vec![("goals".to_string(), DType::Uint8)], ..CsvOptions::default() };
+        let df = DataFrame::read_str_with("club,goals\nInter,200\nInter,100\nRoma,7\n".to_string(), &options)?;
+        let sums = df.group_by(&["club"])?.sum("goals")?;
+
+        assert_eq!(sums.col("goals"), Some(vec![&Val::Uint64(300), &Val::Uint64(7)]));
+        assert_eq!(sums.col("goals"), Some(vec![&reduce::sum(&df.col("goals").unwrap()[..2])?, &Val::Uint64(7)]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn group_by_many() -> Result<(), Error> {
+        let df = df();
+        let grouped = df.group_by(&["club", "nationality"])?;
+
+        assert_eq!(grouped.len(), 5);
+        assert_eq!(df.group_by(&["club"])?.groups()[2], vec![2, 4]);
+        assert!(matches!(df.group_by(&["league"]), Err(Error::HeaderNotFound(_))));
+
+        let longest = df.group_by(&["club"])?.agg("name", |names| {
+            Ok(names.iter().max_by_key(|name| name.to_string().len()).map_or(Val::Null, |&name| name.clone()))
+        })?;
+        assert_eq!(longest.col("name").map(|col| col[2].clone()), Some(Val::String("M. Balotelli".into())));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use crate::{categorical::shared_categories, DataFrame, Error, Val};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JoinType {
+    // only rows with a match on both sides
+    #[default]
+    Inner,
+    // every left row, with nulls where the right side has no match
+    Left,
+}
+
+impl DataFrame {
+    // Joins the rows of `other` whose `on` columns equal those of `self`.
+    // The result holds the columns of `self`, then the other columns of
+    // `other`, suffixed with `_right` when a name is already taken; a name
+    // that still clashes is an `Error::DuplicateHeader`. Null keys never
+    // match.
+    pub fn join(&self, other: &DataFrame, on: &[&str], how: JoinType) -> Result<DataFrame, Error> {
+        let left_keys = on.iter().map(|header| self.position(header)).collect::<Result<Vec<_>, Error>>()?;
+        let right_keys = on.iter().map(|header| other.position(header)).collect::<Result<Vec<_>, Error>>()?;
+        let right_cols = (0..other.width).filter(|col| !right_keys.contains(col)).collect::<Vec<_>>();
+
+        let mut headers = self.headers.clone();
+        for &col in &right_cols {
+            let header = &other.headers[col];
+            let header = if self.headers.contains(header) { format!("{header}_right") } else { header.clone() };
+            if headers.contains(&header) {
+                return Err(Error::DuplicateHeader(header));
+            }
+            headers.push(header);
+        }
+
+        let matches = match_rows(self, &left_keys, other, &right_keys);
+        let mut data = Vec::new();
+        let mut height = 0;
+        for (row, matched) in matches.iter().enumerate() {
+            let left = &self.data[row * self.width..(row + 1) * self.width];
+            if matched.is_empty() && how == JoinType::Left {
+                data.extend_from_slice(left);
+                data.extend(right_cols.iter().map(|_| Val::Null));
+                height += 1;
+            }
+            for &right in matched {
+                data.extend_from_slice(left);
+                data.extend(right_cols.iter().map(|col| other.data[right * other.width + col].clone()));
+                height += 1;
+            }
+        }
+
+        Ok(DataFrame::new(headers, data, self.width + right_cols.len(), height))
+    }
+}
+
+// The code in `left` of each category of `right`, if it has one there.
+fn remap(left: &[String], right: &[String]) -> Vec<Option<u32>> {
+    let codes = left.iter().enumerate().map(|(code, category)| (category.as_str(), code as u32)).collect::<HashMap<_, _>>();
+    right.iter().map(|category| codes.get(category.as_str()).copied()).collect()
+}
+
+// The rows of `right` matching each row of `left`.
+fn match_rows(left: &DataFrame, left_keys: &[usize], right: &DataFrame, right_keys: &[usize]) -> Vec<Vec<usize>> {
+    // Categorical keys match on their codes, those of the right dictionary
+    // mapped once onto the left one.
+    if let ([l], [r]) = (left_keys, right_keys) {
+        if let Some((lc, rc)) = shared_categories(left.column_values(*l)).zip(shared_categories(right.column_values(*r))) {
+            let codes = remap(&lc, &rc);
+            let mut index = vec![Vec::new(); lc.len()];
+            for (row, val) in right.column_values(*r).enumerate() {
+                let Val::Categorical(c) = val else { continue };
+                if let Some(code) = codes[c.code() as usize] {
+                    index[code as usize].push(row);
+                }
+            }
+            return left
+                .column_values(*l)
+                .map(|val| match val {
+                    Val::Categorical(c) => index[c.code() as usize].clone(),
+                    _ => Vec::new(),
+                })
+                .collect();
+        }
+    }
+
+    let key = |df: &DataFrame, keys: &[usize], row: usize| -> Option<Vec<Val>> {
+        keys.iter()
+            .map(|col| Some(&df.data[row * df.width + col]).filter(|val| !val.is_null()).cloned())
+            .collect()
+    };
+
+    let mut index = HashMap::<Vec<Val>, Vec<usize>>::new();
+    for row in 0..right.height {
+        if let Some(key) = key(right, right_keys, row) {
+            index.entry(key).or_default().push(row);
+        }
+    }
+    (0..left.height)
+        .map(|row| key(left, left_keys, row).and_then(|key| index.get(&key).cloned()).unwrap_or_default())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn players() -> DataFrame {
+        let csv = "name,nationality,goals
+Lionel Messi,Argentina,66
+C. Ronaldo,Portugal,3
+Darwin Nunez,Uruguay,6969
+M. Balotelli,,888
+";
+        DataFrame::read_str(csv.to_string()).unwrap()
+    }
+
+    fn nations() -> DataFrame {
+        let csv = "nationality,confederation,name
+Portugal,UEFA,Selecao
+Argentina,CONMEBOL,La Albiceleste
+";
+        DataFrame::read_str(csv.to_string()).unwrap()
+    }
+
+    #[test]
+    fn inner_and_left() -> Result<(), Error> {
+        let inner = players().join(&nations(), &["nationality"], JoinType::Inner)?;
+
+        assert_eq!(inner.headers(), &["name", "nationality", "goals", "confederation", "name_right"]);
+        assert_eq!(inner.col("confederation"), Some(vec![&Val::from("CONMEBOL"), &Val::from("UEFA")]));
+
+        let left = players().join(&nations(), &["nationality"], JoinType::Left)?;
+        assert_eq!(left.col("confederation").map(|col| col.len()), Some(4));
+        assert!(left.col("confederation").is_some_and(|col| col[2].is_null() && col[3].is_null()));
+        assert!(matches!(players().join(&nations(), &["club"], JoinType::Inner), Err(Error::HeaderNotFound(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn clashing_suffix() -> Result<(), Error> {
+        let left = DataFrame::read_str("id,name,name_right\n1,Messi,Leo\n".to_string())?;
+        let right = DataFrame::read_str("id,name\n1,Lionel\n".to_string())?;
+        assert!(matches!(left.join(&right, &["id"], JoinType::Inner), Err(Error::DuplicateHeader(h)) if h == "name_right"));
+
+        let right = DataFrame::read_str("id,name,name_right\n1,Lionel,Leo\n".to_string())?;
+        let left = DataFrame::read_str("id,name\n1,Messi\n".to_string())?;
+        assert!(matches!(left.join(&right, &["id"], JoinType::Inner), Err(Error::DuplicateHeader(h)) if h == "name_right"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn categorical_codes() -> Result<(), Error> {
+        let mut players = players();
+        let mut nations = nations();
+        players.insert_column(players.column("nationality")?.to_categorical()?)?;
+        nations.insert_column(nations.column("nationality")?.to_categorical()?)?;
+
+        // dictionaries built apart, in a different order
+        let (Some(Val::Categorical(l)), Some(Val::Categorical(r))) = (players.column_values(1).next(), nations.column_values(0).next()) else {
+            panic!("nationality should be categorical");
+        };
+        assert!(!l.shares_categories(r));
+        assert_eq!(remap(l.categories(), r.categories()), [Some(1), Some(0)]);
+        assert_eq!(remap(r.categories(), l.categories()), [Some(1), Some(0), None]);
+
+        let joined = players.join(&nations, &["nationality"], JoinType::Inner)?;
+        assert_eq!(joined.col("confederation"), Some(vec![&Val::from("CONMEBOL"), &Val::from("UEFA")]));
+        let joined = nations.join(&players, &["nationality"], JoinType::Left)?;
+        assert_eq!(joined.col("goals"), Some(vec![&Val::Int64(3), &Val::Int64(66)]));
+
+        // strings on one side still match on the text
+        nations.insert_column(nations.column("nationality")?.to_strings()?)?;
+        let joined = players.join(&nations, &["nationality"], JoinType::Inner)?;
+        assert_eq!(joined.col("name"), Some(vec![&Val::from("Lionel Messi"), &Val::from("C. Ronaldo")]));
+
+        Ok(())
+    }
+}
@@ -1,8 +1,57 @@
+mod bytes;
+mod categorical;
+mod column;
+mod convert;
+mod csv;
 mod dataframe;
+mod decimal;
+mod dtype;
 mod error;
+mod export;
+mod field;
+mod groupby;
+mod join;
+mod nested;
+mod ops;
+mod record;
+mod reduce;
+mod reshape;
+mod row;
+mod select;
+mod stats;
+mod table;
+mod temporal;
+mod transform;
+mod unicode;
 mod val;
+mod window;
 
+pub use bytes::BytesEncoding;
+pub use categorical::Categorical;
+pub use column::Column;
+pub use csv::CsvOptions;
 pub use dataframe::DataFrame;
+pub use decimal::{Decimal, Rounding};
+pub use dtype::DType;
 pub use error::Error;
+pub use field::Field;
+pub use groupby::GroupBy;
+pub use join::JoinType;
 pub use macros;
+pub use nested::Struct;
+pub use record::Record;
+pub use reduce::Interpolation;
+pub use reshape::Agg;
+pub use row::Row;
+pub use table::FormatOptions;
+pub use temporal::{Date, Datetime, Duration, Time};
+pub use transform::RankMethod;
 pub use val::Val;
+pub use window::Window;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::categorical::variant;
+    pub use crate::csv::{cell, positions, split};
+    pub use crate::record::Schema;
+}
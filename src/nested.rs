@@ -0,0 +1,238 @@
+use std::fmt;
+
+use crate::{Column, DataFrame, Error, GroupBy, Val};
+
+// A record of named values held in a single cell.
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Struct {
+    fields: Vec<(String, Val)>,
+}
+
+impl Struct {
+    pub fn new<S: Into<String>, I: IntoIterator<Item = (S, Val)>>(fields: I) -> Self {
+        let fields = fields.into_iter().map(|(name, val)| (name.into(), val)).collect();
+        Self { fields }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Val> {
+        self.fields.iter().find(|(n, _)| n == name).map(|(_, val)| val)
+    }
+
+    pub fn fields(&self) -> &[(String, Val)] {
+        &self.fields
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+impl fmt::Display for Struct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("{")?;
+        for (i, (name, val)) in self.fields.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}: {}", name, val)?;
+        }
+        f.write_str("}")
+    }
+}
+
+impl fmt::Debug for Struct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.fields.iter().map(|(name, val)| (name, val))).finish()
+    }
+}
+
+impl DataFrame {
+    // One row per element of the lists in `column`, the other columns being
+    // repeated. Empty lists and nulls give a single row holding null, values
+    // that are not lists are kept as they are.
+    pub fn explode(&self, column: &str) -> Result<DataFrame, Error> {
+        let col = self.position(column)?;
+        let mut data = Vec::with_capacity(self.data.len());
+        let mut height = 0;
+
+        for row in self.data.chunks(self.width.max(1)).take(self.height) {
+            let mut push = |val: Val| {
+                data.extend_from_slice(&row[..col]);
+                data.push(val);
+                data.extend_from_slice(&row[col + 1..]);
+                height += 1;
+            };
+            match &row[col] {
+                Val::List(vals) if !vals.is_empty() => vals.iter().cloned().for_each(&mut push),
+                Val::List(_) => push(Val::Null),
+                val => push(val.clone()),
+            }
+        }
+
+        Ok(DataFrame::new(self.headers.clone(), data, self.width, height))
+    }
+
+    // Replaces a struct column by one column per field, named like
+    // `column.field` in the order fields first appear. Missing fields and
+    // null cells give nulls.
+    pub fn unnest(&self, column: &str) -> Result<DataFrame, Error> {
+        let col = self.position(column)?;
+        let mut names = Vec::<&str>::new();
+        for val in self.column_values(col) {
+            match val {
+                Val::Struct(s) => s.fields.iter().for_each(|(name, _)| {
+                    if !names.contains(&name.as_str()) {
+                        names.push(name);
+                    }
+                }),
+                Val::Null => {},
+                _ => return Err(Error::UnsupportedOperation(format!("unnest {}", val.dtype()))),
+            }
+        }
+
+        let mut headers = self.headers[..col].to_vec();
+        headers.extend(names.iter().map(|name| format!("{column}.{name}")));
+        headers.extend_from_slice(&self.headers[col + 1..]);
+
+        let width = self.width - 1 + names.len();
+        let mut data = Vec::with_capacity(width * self.height);
+        for row in self.data.chunks(self.width.max(1)).take(self.height) {
+            data.extend_from_slice(&row[..col]);
+            data.extend(names.iter().map(|name| match &row[col] {
+                Val::Struct(s) => s.get(name).cloned().unwrap_or(Val::Null),
+                _ => Val::Null,
+            }));
+            data.extend_from_slice(&row[col + 1..]);
+        }
+
+        Ok(DataFrame::new(headers, data, width, self.height))
+    }
+}
+
+impl GroupBy<'_> {
+    // collects the values of `column` within each group into a list
+    pub fn implode(&self, column: &str) -> Result<DataFrame, Error> {
+        self.agg(column, |vals| Ok(Val::List(vals.iter().map(|&val| val.clone()).collect())))
+    }
+}
+
+impl Column {
+    // The element at `index` of every list, counting from the end when
+    // negative. Out of range elements and nulls give null.
+    pub fn list_get(&self, index: isize) -> Result<Column, Error> {
+        self.try_map(|val| match val {
+            Val::List(vals) => {
+                let index = if index < 0 { vals.len().checked_sub(index.unsigned_abs()) } else { Some(index as usize) };
+                Ok(index.and_then(|index| vals.get(index)).cloned().unwrap_or(Val::Null))
+            },
+            Val::Null => Ok(Val::Null),
+            _ => Err(Error::UnsupportedOperation(format!("list element of {}", val.dtype()))),
+        })
+    }
+
+    // the value of the field `name` of every struct, named after the field
+    pub fn field(&self, name: &str) -> Result<Column, Error> {
+        let values = self.try_map(|val| match val {
+            Val::Struct(s) => Ok(s.get(name).cloned().unwrap_or(Val::Null)),
+            Val::Null => Ok(Val::Null),
+            _ => Err(Error::UnsupportedOperation(format!("field {name} of {}", val.dtype()))),
+        })?;
+        Ok(values.rename(name))
+    }
+
+    // the number of elements of every list
+    pub fn list_len(&self) -> Result<Column, Error> {
+        self.try_map(|val| match val {
+            Val::List(vals) => Ok(Val::Usize(vals.len())),
+            Val::Null => Ok(Val::Null),
+            _ => Err(Error::UnsupportedOperation(format!("list length of {}", val.dtype()))),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn df() -> Result<DataFrame, Error> {
+        let mut df = DataFrame::read_str("name,goals\nLionel Messi,66\nC. Ronaldo,3\nDarwin Nunez,6969\n".to_string())?;
+        let tags = vec![
+            Val::from(vec!["captain", "left-footed"]),
+            Val::List(Vec::new()),
+            Val::Null,
+        ];
+        df.insert_column(Column::new("tags", tags))?;
+        Ok(df)
+    }
+
+    #[test]
+    fn explode_and_implode() -> Result<(), Error> {
+        let df = df()?;
+        let exploded = df.explode("tags")?;
+
+        assert_eq!(exploded.col("name").map(|col| col.len()), Some(4));
+        assert_eq!(exploded.col("tags"), Some(vec![&Val::from("captain"), &Val::from("left-footed"), &Val::Null, &Val::Null]));
+        assert_eq!(exploded.col("goals").map(|col| col[1].clone()), Some(Val::Int64(66)));
+        assert!(matches!(df.explode("club"), Err(Error::HeaderNotFound(_))));
+
+        let imploded = exploded.group_by(&["name"])?.implode("tags")?;
+        assert_eq!(imploded.col("tags").map(|col| col[0].clone()), df.col("tags").map(|col| col[0].clone()));
+        assert_eq!(imploded.col("tags").map(|col| col[1].clone()), Some(Val::from(vec![Val::Null])));
+
+        Ok(())
+    }
+
+    #[test]
+    fn element_access() -> Result<(), Error> {
+        let tags = df()?.column("tags")?;
+
+        assert_eq!(tags.list_get(0)?.values(), &[Val::from("captain"), Val::Null, Val::Null]);
+        assert_eq!(tags.list_get(-1)?.values()[0], Val::from("left-footed"));
+        assert_eq!(tags.list_get(-3)?.values()[0], Val::Null);
+        assert_eq!(tags.list_len()?.values(), &[Val::Usize(2), Val::Usize(0), Val::Null]);
+        assert!(matches!(Column::new("x", vec![Val::Int8(1)]).list_get(0), Err(Error::UnsupportedOperation(_))));
+        assert_eq!(Val::from(vec![1u8, 2]).to_string(), "[1, 2]");
+
+        Ok(())
+    }
+
+    fn players() -> Result<DataFrame, Error> {
+        let mut df = DataFrame::read_str("name,goals\nLionel Messi,66\nC. Ronaldo,3\nDarwin Nunez,6969\n".to_string())?;
+        let clubs = vec![
+            Val::Struct(Struct::new([("name", Val::from("Barcelona")), ("founded", Val::Int32(1899))])),
+            Val::Null,
+            Val::Struct(Struct::new([("name", Val::from("Liverpool")), ("city", Val::from("Liverpool"))])),
+        ];
+        df.insert_column(Column::new("club", clubs))?;
+        Ok(df)
+    }
+
+    #[test]
+    fn unnest_struct() -> Result<(), Error> {
+        let unnested = players()?.unnest("club")?;
+
+        assert_eq!(unnested.headers(), &["name", "goals", "club.name", "club.founded", "club.city"]);
+        assert_eq!(unnested.col("club.founded"), Some(vec![&Val::Int32(1899), &Val::Null, &Val::Null]));
+        assert_eq!(unnested.col("club.city").map(|col| col[2].clone()), Some(Val::from("Liverpool")));
+        assert!(matches!(players()?.unnest("goals"), Err(Error::UnsupportedOperation(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn struct_field() -> Result<(), Error> {
+        let clubs = players()?.column("club")?;
+        let names = clubs.field("name")?;
+
+        assert_eq!(names.name(), "name");
+        assert_eq!(names.values(), &[Val::from("Barcelona"), Val::Null, Val::from("Liverpool")]);
+        assert_eq!(clubs.values()[0].to_string(), "{name: \"Barcelona\", founded: 1899}");
+        assert_eq!(format!("{:?}", clubs.values()[0]), "Struct({\"name\": String(\"Barcelona\"), \"founded\": Int32(1899)})");
+
+        Ok(())
+    }
+}
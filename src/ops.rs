@@ -0,0 +1,527 @@
+use std::ops::{Add, BitAnd, BitOr, Div, Mul, Neg, Not, Rem, Sub};
+
+use crate::{Decimal, DType, Error, Val};
+
+// Arithmetic between values follows these promotion rules:
+// - a float with anything numeric gives `Float64`, two `Float32` stay `Float32`
+// - a decimal with a decimal or an integer stays an exact decimal, quotients
+//   are rounded half to even at the larger scale of both sides, at least six
+// - two integers of the same variant keep that variant
+// - two integers of different variants give the narrowest variant holding
+//   both, e.g. `Int8` with `Uint8` gives `Int16`
+// - integer overflow is an `Error::Overflow`, integer division and remainder
+//   truncate and fail on a zero divisor
+// - strings only support `+`, which concatenates them
+// - null with anything gives null
+// - dates, times and datetimes move by a `Duration` and subtract into one,
+//   a date only moves by whole days and a time wraps around midnight;
+//   durations add up and scale by integers
+//
+// Booleans support `&`, `|` and `!` with three-valued logic: null is an
+// unknown value, so `false & null` is `false` and `true | null` is `true`,
+// while `true & null`, `false | null` and `!null` stay null.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl Op {
+    fn symbol(self) -> &'static str {
+        match self {
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+            Op::Rem => "%",
+        }
+    }
+
+    fn float(self, lhs: f64, rhs: f64) -> f64 {
+        match self {
+            Op::Add => lhs + rhs,
+            Op::Sub => lhs - rhs,
+            Op::Mul => lhs * rhs,
+            Op::Div => lhs / rhs,
+            Op::Rem => lhs % rhs,
+        }
+    }
+
+    fn int(self, lhs: i128, rhs: i128) -> Option<i128> {
+        match self {
+            Op::Add => lhs.checked_add(rhs),
+            Op::Sub => lhs.checked_sub(rhs),
+            Op::Mul => lhs.checked_mul(rhs),
+            Op::Div => lhs.checked_div(rhs),
+            Op::Rem => lhs.checked_rem(rhs),
+        }
+    }
+
+    fn uint(self, lhs: u128, rhs: u128) -> Option<u128> {
+        match self {
+            Op::Add => lhs.checked_add(rhs),
+            Op::Sub => lhs.checked_sub(rhs),
+            Op::Mul => lhs.checked_mul(rhs),
+            Op::Div => lhs.checked_div(rhs),
+            Op::Rem => lhs.checked_rem(rhs),
+        }
+    }
+}
+
+// An integer variant by signedness and width; `isize` and `usize` count as
+// 64 bits wide but are kept apart from `i64` and `u64`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct IntKind {
+    signed: bool,
+    bits: u8,
+    pointer: bool,
+}
+
+impl IntKind {
+    fn of(val: &Val) -> Option<Self> {
+        let (signed, bits, pointer) = match val {
+            Val::Isize(_) => (true, 64, true),
+            Val::Usize(_) => (false, 64, true),
+            Val::Int128(_) => (true, 128, false),
+            Val::UInt128(_) => (false, 128, false),
+            Val::Int64(_) => (true, 64, false),
+            Val::Uint64(_) => (false, 64, false),
+            Val::Int32(_) => (true, 32, false),
+            Val::Uint32(_) => (false, 32, false),
+            Val::Int16(_) => (true, 16, false),
+            Val::Uint16(_) => (false, 16, false),
+            Val::Int8(_) => (true, 8, false),
+            Val::Uint8(_) => (false, 8, false),
+            _ => return None,
+        };
+        Some(Self { signed, bits, pointer })
+    }
+
+    fn common(self, other: Self) -> Self {
+        if self == other {
+            return self;
+        }
+        let (signed, bits) = match (self.signed, other.signed) {
+            (true, true) | (false, false) => (self.signed, self.bits.max(other.bits)),
+            (true, false) => (true, self.bits.max(other.bits.saturating_mul(2)).min(128)),
+            (false, true) => (true, other.bits.max(self.bits.saturating_mul(2)).min(128)),
+        };
+        Self { signed, bits, pointer: false }
+    }
+
+    fn name(self) -> &'static str {
+        match (self.signed, self.bits, self.pointer) {
+            (true, _, true) => "isize",
+            (false, _, true) => "usize",
+            (true, 128, _) => "i128",
+            (false, 128, _) => "u128",
+            (true, 64, _) => "i64",
+            (false, 64, _) => "u64",
+            (true, 32, _) => "i32",
+            (false, 32, _) => "u32",
+            (true, 16, _) => "i16",
+            (false, 16, _) => "u16",
+            (true, _, _) => "i8",
+            (false, _, _) => "u8",
+        }
+    }
+
+    fn wrap_int(self, n: i128) -> Option<Val> {
+        let val = match (self.signed, self.bits, self.pointer) {
+            (true, _, true) => Val::Isize(n.try_into().ok()?),
+            (false, _, true) => Val::Usize(n.try_into().ok()?),
+            (true, 128, _) => Val::Int128(n),
+            (false, 128, _) => Val::UInt128(n.try_into().ok()?),
+            (true, 64, _) => Val::Int64(n.try_into().ok()?),
+            (false, 64, _) => Val::Uint64(n.try_into().ok()?),
+            (true, 32, _) => Val::Int32(n.try_into().ok()?),
+            (false, 32, _) => Val::Uint32(n.try_into().ok()?),
+            (true, 16, _) => Val::Int16(n.try_into().ok()?),
+            (false, 16, _) => Val::Uint16(n.try_into().ok()?),
+            (true, _, _) => Val::Int8(n.try_into().ok()?),
+            (false, _, _) => Val::Uint8(n.try_into().ok()?),
+        };
+        Some(val)
+    }
+
+    fn wrap_uint(self, n: u128) -> Option<Val> {
+        match (self.signed, self.bits, self.pointer) {
+            (false, 128, false) => Some(Val::UInt128(n)),
+            _ => self.wrap_int(n.try_into().ok()?),
+        }
+    }
+}
+
+impl Val {
+    fn as_i128(&self) -> Option<i128> {
+        match *self {
+            Val::Isize(n) => Some(n as i128),
+            Val::Usize(n) => Some(n as i128),
+            Val::Int128(n) => Some(n),
+            Val::UInt128(n) => n.try_into().ok(),
+            Val::Int64(n) => Some(n as i128),
+            Val::Uint64(n) => Some(n as i128),
+            Val::Int32(n) => Some(n as i128),
+            Val::Uint32(n) => Some(n as i128),
+            Val::Int16(n) => Some(n as i128),
+            Val::Uint16(n) => Some(n as i128),
+            Val::Int8(n) => Some(n as i128),
+            Val::Uint8(n) => Some(n as i128),
+            _ => None,
+        }
+    }
+
+    fn as_u128(&self) -> Option<u128> {
+        match *self {
+            Val::UInt128(n) => Some(n),
+            _ => self.as_i128()?.try_into().ok(),
+        }
+    }
+
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Val::Float64(n) => Some(n),
+            Val::Float32(n) => Some(n as f64),
+            Val::UInt128(n) => Some(n as f64),
+            Val::Decimal(d) => Some(d.to_f64()),
+            _ => self.as_i128().map(|n| n as f64),
+        }
+    }
+
+    // decimals and integers as a decimal, failing for integers above 38 digits
+    fn as_decimal(&self) -> Option<Result<Decimal, Error>> {
+        match self {
+            Val::Decimal(d) => Some(Ok(*d)),
+            _ if IntKind::of(self).is_some() => Some(
+                self.as_i128()
+                    .ok_or_else(|| Error::Overflow(format!("{self} overflows a decimal")))
+                    .and_then(Decimal::from_int)
+            ),
+            _ => None,
+        }
+    }
+
+    fn arithmetic(&self, op: Op, rhs: &Val) -> Result<Val, Error> {
+        let unsupported = || Error::UnsupportedOperation(format!(
+            "{} {} {}", self.dtype(), op.symbol(), rhs.dtype()
+        ));
+        let overflow = |kind: IntKind| Error::Overflow(format!(
+            "{self} {} {rhs} overflows {}", op.symbol(), kind.name()
+        ));
+
+        match (self, rhs) {
+            (Val::Null, _) | (_, Val::Null) => Ok(Val::Null),
+            (Val::Date(_) | Val::Time(_) | Val::Datetime(_) | Val::Duration(_), _)
+            | (_, Val::Date(_) | Val::Time(_) | Val::Datetime(_) | Val::Duration(_)) => self.temporal(op, rhs),
+            (Val::String(s1), Val::String(s2)) if op == Op::Add => Ok(Val::String(format!("{s1}{s2}"))),
+            (Val::Float32(f1), Val::Float32(f2)) => Ok(Val::Float32(op.float(*f1 as f64, *f2 as f64) as f32)),
+            (Val::Float64(_) | Val::Float32(_), _) | (_, Val::Float64(_) | Val::Float32(_)) => {
+                let (Some(f1), Some(f2)) = (self.as_f64(), rhs.as_f64()) else { return Err(unsupported()) };
+                Ok(Val::Float64(op.float(f1, f2)))
+            },
+            (Val::Decimal(_), _) | (_, Val::Decimal(_)) => {
+                let (Some(d1), Some(d2)) = (self.as_decimal(), rhs.as_decimal()) else { return Err(unsupported()) };
+                let val = match op {
+                    Op::Add => d1?.checked_add(&d2?),
+                    Op::Sub => d1?.checked_sub(&d2?),
+                    Op::Mul => d1?.checked_mul(&d2?),
+                    Op::Div => d1?.div_default(&d2?),
+                    Op::Rem => d1?.checked_rem(&d2?),
+                };
+                val.map(Val::Decimal)
+            },
+            _ => {
+                let (Some(k1), Some(k2)) = (IntKind::of(self), IntKind::of(rhs)) else { return Err(unsupported()) };
+                let kind = k1.common(k2);
+                if matches!(op, Op::Div | Op::Rem) && rhs.as_f64() == Some(0.0) {
+                    return Err(Error::DivisionByZero);
+                }
+                let val = if kind.signed {
+                    let (Some(i1), Some(i2)) = (self.as_i128(), rhs.as_i128()) else { return Err(overflow(kind)) };
+                    op.int(i1, i2).and_then(|n| kind.wrap_int(n))
+                } else {
+                    let (Some(u1), Some(u2)) = (self.as_u128(), rhs.as_u128()) else { return Err(overflow(kind)) };
+                    op.uint(u1, u2).and_then(|n| kind.wrap_uint(n))
+                };
+                val.ok_or_else(|| overflow(kind))
+            }
+        }
+    }
+}
+
+impl Val {
+    fn temporal(&self, op: Op, rhs: &Val) -> Result<Val, Error> {
+        let unsupported = || Error::UnsupportedOperation(format!(
+            "{} {} {}", self.dtype(), op.symbol(), rhs.dtype()
+        ));
+        let overflow = || Error::Overflow(format!(
+            "{self} {} {rhs} overflows {}", op.symbol(), self.dtype()
+        ));
+
+        match (self, op, rhs) {
+            (Val::Duration(_), Op::Add, Val::Date(_) | Val::Time(_) | Val::Datetime(_)) => rhs.temporal(op, self),
+            (Val::Date(date), Op::Add | Op::Sub, Val::Duration(duration)) => {
+                let days = duration
+                    .whole_days()
+                    .ok_or_else(|| Error::InexactConversion { value: duration.to_string(), to: DType::Date })?;
+                let days = if op == Op::Sub { days.checked_neg() } else { Some(days) };
+                days.and_then(|days| date.checked_add_days(days)).map(Val::Date).ok_or_else(overflow)
+            },
+            (Val::Time(time), Op::Add, Val::Duration(duration)) => Ok(Val::Time(time.wrapping_add(*duration))),
+            (Val::Time(time), Op::Sub, Val::Duration(duration)) => Ok(Val::Time(time.wrapping_sub(*duration))),
+            (Val::Datetime(datetime), Op::Add, Val::Duration(duration)) => datetime.checked_add(*duration).map(Val::Datetime).ok_or_else(overflow),
+            (Val::Datetime(datetime), Op::Sub, Val::Duration(duration)) => datetime.checked_sub(*duration).map(Val::Datetime).ok_or_else(overflow),
+            (Val::Date(d1), Op::Sub, Val::Date(d2)) => d1.duration_since(*d2).map(Val::Duration).ok_or_else(overflow),
+            (Val::Time(t1), Op::Sub, Val::Time(t2)) => Ok(Val::Duration(t1.duration_since(*t2))),
+            (Val::Datetime(d1), Op::Sub, Val::Datetime(d2)) => {
+                // an instant can't be compared with a wall clock time
+                if d1.offset().is_some() != d2.offset().is_some() {
+                    return Err(unsupported());
+                }
+                d1.duration_since(*d2).map(Val::Duration).ok_or_else(overflow)
+            },
+            (Val::Duration(d1), Op::Add, Val::Duration(d2)) => d1.checked_add(*d2).map(Val::Duration).ok_or_else(overflow),
+            (Val::Duration(d1), Op::Sub, Val::Duration(d2)) => d1.checked_sub(*d2).map(Val::Duration).ok_or_else(overflow),
+            (Val::Duration(duration), Op::Mul | Op::Div, n) | (n, Op::Mul, Val::Duration(duration)) => {
+                let Some(n) = n.as_i128() else { return Err(unsupported()) };
+                if op == Op::Div && n == 0 {
+                    return Err(Error::DivisionByZero);
+                }
+                let n = i64::try_from(n).map_err(|_| overflow())?;
+                let val = if op == Op::Mul { duration.checked_mul(n) } else { duration.checked_div(n) };
+                val.map(Val::Duration).ok_or_else(overflow)
+            },
+            _ => Err(unsupported()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Logic {
+    And,
+    Or,
+}
+
+impl Logic {
+    fn symbol(self) -> &'static str {
+        match self {
+            Logic::And => "&",
+            Logic::Or => "|",
+        }
+    }
+}
+
+impl Val {
+    fn logic(&self, op: Logic, rhs: &Val) -> Result<Val, Error> {
+        let as_bool = |val: &Val| match val {
+            Val::Bool(b) => Ok(Some(*b)),
+            Val::Null => Ok(None),
+            _ => Err(Error::UnsupportedOperation(format!(
+                "{} {} {}", self.dtype(), op.symbol(), rhs.dtype()
+            ))),
+        };
+        let (lhs, rhs) = (as_bool(self)?, as_bool(rhs)?);
+        let val = match op {
+            Logic::And => match (lhs, rhs) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            Logic::Or => match (lhs, rhs) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+        };
+        Ok(val.map_or(Val::Null, Val::Bool))
+    }
+}
+
+macro_rules! impl_op {
+    ($($trait:ident, $fn:ident, $method:ident, $op:expr;)*) => {
+        $(
+            impl $trait<&Val> for &Val {
+                type Output = Result<Val, Error>;
+
+                fn $fn(self, rhs: &Val) -> Self::Output {
+                    self.$method($op, rhs)
+                }
+            }
+
+            impl $trait<Val> for Val {
+                type Output = Result<Val, Error>;
+
+                fn $fn(self, rhs: Val) -> Self::Output {
+                    self.$method($op, &rhs)
+                }
+            }
+
+            impl $trait<&Val> for Val {
+                type Output = Result<Val, Error>;
+
+                fn $fn(self, rhs: &Val) -> Self::Output {
+                    self.$method($op, rhs)
+                }
+            }
+
+            impl $trait<Val> for &Val {
+                type Output = Result<Val, Error>;
+
+                fn $fn(self, rhs: Val) -> Self::Output {
+                    self.$method($op, &rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_op! {
+    Add, add, arithmetic, Op::Add;
+    Sub, sub, arithmetic, Op::Sub;
+    Mul, mul, arithmetic, Op::Mul;
+    Div, div, arithmetic, Op::Div;
+    Rem, rem, arithmetic, Op::Rem;
+    BitAnd, bitand, logic, Logic::And;
+    BitOr, bitor, logic, Logic::Or;
+}
+
+impl Neg for &Val {
+    type Output = Result<Val, Error>;
+
+    fn neg(self) -> Self::Output {
+        let overflow = || Error::Overflow(format!("-{self} overflows {}", self.dtype()));
+        match *self {
+            Val::Isize(n) => n.checked_neg().map(Val::Isize).ok_or_else(overflow),
+            Val::Int128(n) => n.checked_neg().map(Val::Int128).ok_or_else(overflow),
+            Val::Int64(n) => n.checked_neg().map(Val::Int64).ok_or_else(overflow),
+            Val::Int32(n) => n.checked_neg().map(Val::Int32).ok_or_else(overflow),
+            Val::Int16(n) => n.checked_neg().map(Val::Int16).ok_or_else(overflow),
+            Val::Int8(n) => n.checked_neg().map(Val::Int8).ok_or_else(overflow),
+            Val::Float64(n) => Ok(Val::Float64(-n)),
+            Val::Float32(n) => Ok(Val::Float32(-n)),
+            Val::Decimal(d) => d.checked_neg().map(Val::Decimal).ok_or_else(overflow),
+            Val::Duration(d) => d.checked_neg().map(Val::Duration).ok_or_else(overflow),
+            Val::Null => Ok(Val::Null),
+            _ => Err(Error::UnsupportedOperation(format!("-{}", self.dtype()))),
+        }
+    }
+}
+
+impl Neg for Val {
+    type Output = Result<Val, Error>;
+
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+impl Not for &Val {
+    type Output = Result<Val, Error>;
+
+    fn not(self) -> Self::Output {
+        match self {
+            Val::Bool(b) => Ok(Val::Bool(!b)),
+            Val::Null => Ok(Val::Null),
+            _ => Err(Error::UnsupportedOperation(format!("!{}", self.dtype()))),
+        }
+    }
+}
+
+impl Not for Val {
+    type Output = Result<Val, Error>;
+
+    fn not(self) -> Self::Output {
+        !&self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Duration;
+
+    #[test]
+    fn promotion() -> Result<(), Error> {
+        assert!(matches!((Val::Int32(2) + Val::Int32(3))?, Val::Int32(5)));
+        assert!(matches!((Val::Int8(-2) * Val::Uint8(100))?, Val::Int16(-200)));
+        assert!(matches!((Val::Uint16(2) + Val::Uint64(3))?, Val::Uint64(5)));
+        assert!(matches!((Val::Usize(66) / Val::Float64(2.0))?, Val::Float64(33.0)));
+        assert!(matches!((Val::Float32(0.5) + Val::Float32(0.25))?, Val::Float32(0.75)));
+        assert!(matches!((Val::Float32(0.5) + Val::Int8(1))?, Val::Float64(1.5)));
+        assert!(matches!((Val::Int64(7) % Val::Int64(-4))?, Val::Int64(3)));
+        assert_eq!((Val::String("Nunez".into()) + Val::String("!".into()))?, Val::String("Nunez!".into()));
+        assert_eq!((Val::Null * Val::Int8(2))?, Val::Null);
+        assert_eq!((-Val::Null)?, Val::Null);
+
+        Ok(())
+    }
+
+    #[test]
+    fn overflow() {
+        assert!(matches!(Val::Uint8(200) + Val::Uint8(100), Err(Error::Overflow(_))));
+        assert!(matches!(Val::Uint8(1) - Val::Uint8(2), Err(Error::Overflow(_))));
+        assert!(matches!(Val::UInt128(u128::MAX) - Val::Int8(1), Err(Error::Overflow(_))));
+        assert!(matches!(Val::UInt128(u128::MAX) + Val::Uint8(1), Err(Error::Overflow(_))));
+        assert!(matches!(Val::UInt128(u128::MAX) - Val::Uint8(1), Ok(Val::UInt128(n)) if n == u128::MAX - 1));
+        assert!(matches!(-Val::Int8(i8::MIN), Err(Error::Overflow(_))));
+        assert!(matches!(Val::Int64(1) / Val::Uint8(0), Err(Error::DivisionByZero)));
+        assert!(matches!(Val::String("1".into()) * Val::Int8(2), Err(Error::UnsupportedOperation(_))));
+        assert!(matches!(-Val::Uint8(2), Err(Error::UnsupportedOperation(_))));
+    }
+
+    #[test]
+    fn three_valued_logic() -> Result<(), Error> {
+        assert_eq!((Val::Bool(false) & Val::Null)?, Val::Bool(false));
+        assert_eq!((Val::Bool(true) & Val::Null)?, Val::Null);
+        assert_eq!((Val::Bool(true) | Val::Null)?, Val::Bool(true));
+        assert_eq!((Val::Null | Val::Bool(false))?, Val::Null);
+        assert_eq!((!Val::Bool(true))?, Val::Bool(false));
+        assert_eq!((!Val::Null)?, Val::Null);
+        assert!(matches!(Val::Bool(true) & Val::Int8(1), Err(Error::UnsupportedOperation(_))));
+        assert!(matches!(!Val::Int8(1), Err(Error::UnsupportedOperation(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn temporal() -> Result<(), Error> {
+        let date = Val::Date("2024-05-12".parse()?);
+        let week = Val::Duration(Duration::from_days(7).unwrap());
+        let kickoff = Val::Datetime("2024-05-12T20:00:00+01:00".parse()?);
+
+        assert_eq!((&date + &week)?.to_string(), "2024-05-19");
+        assert_eq!((&week + &date)?, (&date + &week)?);
+        assert_eq!((Val::Date("2024-06-01".parse()?) - &date)?, Val::Duration(Duration::from_days(20).unwrap()));
+        assert_eq!((&kickoff + Val::Duration(Duration::from_minutes(105).unwrap()))?.to_string(), "2024-05-12T21:45:00+01:00");
+        assert_eq!((&week * Val::Int8(2))?, Val::Duration(Duration::from_days(14).unwrap()));
+        assert_eq!((&week / Val::Int8(7))?, Val::Duration(Duration::from_days(1).unwrap()));
+        assert_eq!((-&week)?, Val::Duration(Duration::from_days(-7).unwrap()));
+
+        let hours = Val::Duration(Duration::from_hours(3).unwrap());
+        assert!(matches!(&date + &hours, Err(Error::InexactConversion { to: DType::Date, .. })));
+        assert!(matches!(&date + &date, Err(Error::UnsupportedOperation(_))));
+        assert!(matches!(&kickoff - Val::Datetime("2024-05-12T20:00:00".parse()?), Err(Error::UnsupportedOperation(_))));
+        assert!(matches!(&week / Val::Int8(0), Err(Error::DivisionByZero)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn decimal() -> Result<(), Error> {
+        let price = Val::Decimal("19.99".parse()?);
+        let total = [&price, &price, &price].into_iter().try_fold(Val::Decimal("0".parse()?), |acc, val| acc + val)?;
+
+        assert_eq!(total.to_string(), "59.97");
+        assert_eq!((&price * Val::Int8(3))?, total);
+        assert_eq!((&total / Val::Int8(3))?.to_string(), "19.990000");
+        assert!(matches!((&price + Val::Float64(0.01))?, Val::Float64(_)));
+        assert_eq!((-&price)?.to_string(), "-19.99");
+        assert!(matches!(&price / Val::Decimal("0.00".parse()?), Err(Error::DivisionByZero)));
+
+        Ok(())
+    }
+}
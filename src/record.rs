@@ -0,0 +1,108 @@
+use std::{borrow::Cow, slice::Iter};
+
+use crate::{DType, Error, Val};
+
+// Implemented by the `DataFrame` derive. Besides backing the generated
+// `read_*` functions, it lets a derived struct be embedded into another one
+// through `#[dataframe(flatten)]`.
+//
+// `SCHEMA` lists the column of every leaf field in declaration order,
+// flattened fields contributing theirs under their prefix. A row is walked
+// with `positions`, which yields the csv column of each of them in turn.
+pub trait Record<'src>: Sized {
+    const SCHEMA: &'static [(&'static str, DType)];
+
+    fn parse_row(row: &[Cow<'src, str>], positions: &mut Iter<'_, usize>) -> Result<Self, Error>;
+
+    fn parse_vals(row: &[Cow<'_, str>], positions: &mut Iter<'_, usize>, data: &mut Vec<Val>) -> Result<(), Error>;
+
+    fn to_vals(&self, data: &mut Vec<Val>);
+}
+
+// Caps of the `DataFrame` derive, checked when a struct's `SCHEMA` is
+// evaluated. Every derived struct keeps buffers of this size in its binary.
+const MAX_COLUMNS: usize = 1024;
+const MAX_NAME_BYTES: usize = 32 * 1024;
+
+// Builds the `SCHEMA` of a derived struct at compile time, joining the
+// prefix of a flattened field to the names of its columns. Stable const
+// evaluation can't size an array after the schema of a generic field, so
+// the names and columns live in fixed buffers.
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct Schema {
+    names: [u8; MAX_NAME_BYTES],
+    len: usize,
+    // the range of `names` holding each column's name
+    columns: [(usize, usize, DType); MAX_COLUMNS],
+    width: usize,
+}
+
+#[doc(hidden)]
+pub struct Columns {
+    columns: [(&'static str, DType); MAX_COLUMNS],
+    width: usize,
+}
+
+impl Schema {
+    pub const fn new() -> Self {
+        Self {
+            names: [0; MAX_NAME_BYTES],
+            len: 0,
+            columns: [(0, 0, DType::Null); MAX_COLUMNS],
+            width: 0,
+        }
+    }
+
+    pub const fn column(&mut self, prefix: &str, name: &str, dtype: DType) {
+        assert!(self.width < MAX_COLUMNS, "a derived struct holds at most 1024 columns");
+        assert!(
+            self.len + prefix.len() + name.len() <= MAX_NAME_BYTES,
+            "the column names of a derived struct take at most 32 KiB"
+        );
+        let start = self.len;
+        let (prefix, name) = (prefix.as_bytes(), name.as_bytes());
+        let mut i = 0;
+        while i < prefix.len() + name.len() {
+            self.names[self.len] = if i < prefix.len() { prefix[i] } else { name[i - prefix.len()] };
+            self.len += 1;
+            i += 1;
+        }
+        self.columns[self.width] = (start, self.len, dtype);
+        self.width += 1;
+    }
+
+    pub const fn flatten(&mut self, prefix: &str, schema: &[(&str, DType)]) {
+        let mut i = 0;
+        while i < schema.len() {
+            self.column(prefix, schema[i].0, schema[i].1);
+            i += 1;
+        }
+    }
+
+    pub const fn columns(&'static self) -> Columns {
+        let mut columns = [("", DType::Null); MAX_COLUMNS];
+        let mut i = 0;
+        while i < self.width {
+            let (start, end, dtype) = self.columns[i];
+            let (_, name) = self.names.split_at(end).0.split_at(start);
+            // the bytes of whole `&str`s, so always valid
+            let Ok(name) = std::str::from_utf8(name) else { unreachable!() };
+            columns[i] = (name, dtype);
+            i += 1;
+        }
+        Columns { columns, width: self.width }
+    }
+}
+
+impl Default for Schema {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Columns {
+    pub const fn as_slice(&'static self) -> &'static [(&'static str, DType)] {
+        self.columns.split_at(self.width).0
+    }
+}
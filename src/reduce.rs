@@ -0,0 +1,228 @@
+use crate::{stats, Column, DataFrame, Error, Val};
+
+// How `quantile` picks a value when the quantile falls between two of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    // the straight line between the two values
+    #[default]
+    Linear,
+    // the smaller value
+    Lower,
+    // the larger value
+    Higher,
+    // the closer value, the one at an even position when halfway
+    Nearest,
+    // the mean of the two values
+    Midpoint,
+}
+
+pub(crate) fn number<'a>(val: &'a Val, op: &str) -> Result<&'a Val, Error> {
+    if val.dtype().is_numeric() {
+        Ok(val)
+    } else {
+        Err(Error::UnsupportedOperation(format!("{op} of {}", val.dtype())))
+    }
+}
+
+// The non-null values, failing on any that isn't a number.
+fn numbers<'a>(values: &[&'a Val], op: &str) -> Result<Vec<&'a Val>, Error> {
+    values.iter().filter(|val| !val.is_null()).map(|val| number(val, op)).collect()
+}
+
+fn floats(values: &[&Val], op: &str) -> Result<Vec<f64>, Error> {
+    Ok(numbers(values, op)?.into_iter().filter_map(Val::as_f64).collect())
+}
+
+// Integers are summed in 64 bits at least and `f32` as `f64`, so that the
+// sum of small types doesn't overflow them.
+pub(crate) fn widen(val: &Val) -> Val {
+    match *val {
+        Val::Int8(n) => Val::Int64(n as i64),
+        Val::Int16(n) => Val::Int64(n as i64),
+        Val::Int32(n) => Val::Int64(n as i64),
+        Val::Uint8(n) => Val::Uint64(n as u64),
+        Val::Uint16(n) => Val::Uint64(n as u64),
+        Val::Uint32(n) => Val::Uint64(n as u64),
+        Val::Float32(n) => Val::Float64(n as f64),
+        ref val => val.clone(),
+    }
+}
+
+// The sum of the numbers, or the count of true values of booleans. Mixed
+// kinds of numbers are promoted like in arithmetic, and a sum that doesn't
+// fit even so is an `Error::Overflow`. This takes over the boolean-only
+// `Column::sum`, which gave 0 rather than null when there were no values
+// and failed on numbers rather than adding them up.
+pub(crate) fn sum(values: &[&Val]) -> Result<Val, Error> {
+    let present = values.iter().filter(|val| !val.is_null());
+    if present.clone().all(|val| val.is_bool()) && present.clone().next().is_some() {
+        return Ok(Val::Usize(present.filter(|val| matches!(val, Val::Bool(true))).count()));
+    }
+    numbers(values, "sum")?
+        .into_iter()
+        .try_fold(Val::Null, |acc, val| if acc.is_null() { Ok(widen(val)) } else { acc + widen(val) })
+}
+
+pub(crate) fn mean(values: &[&Val]) -> Result<Val, Error> {
+    Ok(stats::mean(&floats(values, "mean")?).map_or(Val::Null, Val::Float64))
+}
+
+pub(crate) fn median(values: &[&Val]) -> Result<Val, Error> {
+    quantile(values, 0.5, Interpolation::Linear)
+}
+
+// The `q` quantile of the numbers, a float when interpolated between two of
+// them and one of the numbers otherwise.
+pub(crate) fn quantile(values: &[&Val], q: f64, interpolation: Interpolation) -> Result<Val, Error> {
+    if !(0.0..=1.0).contains(&q) {
+        return Err(Error::FractionOutOfRange(q));
+    }
+    let mut sorted = numbers(values, "quantile")?;
+    sorted.sort();
+    let Some(last) = sorted.len().checked_sub(1) else { return Ok(Val::Null) };
+
+    let pos = q * last as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    let frac = pos - lo as f64;
+    let float = |i: usize| sorted[i].as_f64().unwrap_or(f64::NAN);
+    let val = match interpolation {
+        Interpolation::Lower => sorted[lo].clone(),
+        Interpolation::Higher => sorted[hi].clone(),
+        Interpolation::Nearest if frac < 0.5 || (frac == 0.5 && lo % 2 == 0) => sorted[lo].clone(),
+        Interpolation::Nearest => sorted[hi].clone(),
+        Interpolation::Midpoint => Val::Float64((float(lo) + float(hi)) / 2.0),
+        Interpolation::Linear => Val::Float64(float(lo) + (float(hi) - float(lo)) * frac),
+    };
+    Ok(val)
+}
+
+// the sample variance, null for fewer than two numbers
+pub(crate) fn var(values: &[&Val]) -> Result<Val, Error> {
+    Ok(stats::var(&floats(values, "var")?, 1).map_or(Val::Null, Val::Float64))
+}
+
+pub(crate) fn std(values: &[&Val]) -> Result<Val, Error> {
+    Ok(stats::var(&floats(values, "std")?, 1).map_or(Val::Null, |var| Val::Float64(var.sqrt())))
+}
+
+// The extremes work on any values, ordered like `Val` is.
+pub(crate) fn min(values: &[&Val]) -> Result<Val, Error> {
+    Ok(values.iter().filter(|val| !val.is_null()).min().map_or(Val::Null, |&val| val.clone()))
+}
+
+pub(crate) fn max(values: &[&Val]) -> Result<Val, Error> {
+    Ok(values.iter().filter(|val| !val.is_null()).max().map_or(Val::Null, |&val| val.clone()))
+}
+
+// the most frequent value, the first to appear winning a tie
+pub(crate) fn mode(values: &[&Val]) -> Result<Val, Error> {
+    let present = values.iter().copied().filter(|val| !val.is_null());
+    Ok(stats::mode(present).map_or(Val::Null, |(val, _)| val.clone()))
+}
+
+// Every reduction on a `Column`, and on a column of a `DataFrame` by name,
+// skipping nulls and giving null when there are no values.
+macro_rules! impl_reductions {
+    ($($name:ident),* $(,)?) => {
+        impl Column {
+            $(
+                pub fn $name(&self) -> Result<Val, Error> {
+                    $name(&self.iter().collect::<Vec<_>>())
+                }
+            )*
+
+            pub fn quantile(&self, q: f64, interpolation: Interpolation) -> Result<Val, Error> {
+                quantile(&self.iter().collect::<Vec<_>>(), q, interpolation)
+            }
+        }
+
+        impl DataFrame {
+            $(
+                pub fn $name(&self, column: &str) -> Result<Val, Error> {
+                    $name(&self.column_values(self.position(column)?).collect::<Vec<_>>())
+                }
+            )*
+
+            pub fn quantile(&self, column: &str, q: f64, interpolation: Interpolation) -> Result<Val, Error> {
+                quantile(&self.column_values(self.position(column)?).collect::<Vec<_>>(), q, interpolation)
+            }
+        }
+    };
+}
+
+impl_reductions!(sum, mean, median, var, std, min, max, mode);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DType;
+
+    fn df() -> DataFrame {
+        let csv = "name,nationality,goals,xg
+Lionel Messi,Argentina,66,0.5
+C. Ronaldo,Portugal,3,
+Darwin Nunez,Uruguay,6969,2.5
+Paulo Dybala,Argentina,20,1.0
+";
+        DataFrame::read_str(csv.to_string()).unwrap()
+    }
+
+    #[test]
+    fn numeric_reductions() -> Result<(), Error> {
+        let df = df();
+
+        assert_eq!(df.sum("goals")?, Val::Int64(7058));
+        assert_eq!(df.sum("xg")?, Val::Float64(4.0));
+        assert_eq!(df.mean("xg")?, Val::Float64(4.0 / 3.0));
+        assert_eq!(df.median("goals")?, Val::Float64(43.0));
+        assert_eq!(df.quantile("xg", 0.9, Interpolation::Linear)?, Val::Float64(2.2));
+        assert_eq!(df.quantile("goals", 0.5, Interpolation::Lower)?, Val::Int64(20));
+        assert_eq!(df.quantile("goals", 0.5, Interpolation::Higher)?, Val::Int64(66));
+        assert_eq!(df.quantile("goals", 0.5, Interpolation::Midpoint)?, Val::Float64(43.0));
+        assert_eq!(df.quantile("goals", 1.0 / 6.0, Interpolation::Nearest)?, Val::Int64(3));
+        assert_eq!(df.var("xg")?, Val::Float64(1.0833333333333335));
+        assert_eq!(df.std("goals")?.dtype(), DType::Float64);
+        assert_eq!(df.min("goals")?, Val::Int64(3));
+        assert_eq!(df.max("xg")?, Val::Float64(2.5));
+
+        assert!(matches!(df.mean("name"), Err(Error::UnsupportedOperation(_))));
+        assert!(matches!(df.quantile("xg", 1.5, Interpolation::Linear), Err(Error::FractionOutOfRange(q)) if q == 1.5));
+        assert!(matches!(df.sum("club"), Err(Error::HeaderNotFound(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn promotion_and_nulls() -> Result<(), Error> {
+        let bytes = Column::new("bytes", vec![Val::Uint8(200), Val::Null, Val::Uint8(100)]);
+        assert_eq!(bytes.sum()?, Val::Uint64(300));
+
+        let mixed = Column::new("mixed", vec![Val::Int8(-1), Val::Uint32(2), Val::Float32(0.5)]);
+        assert_eq!(mixed.sum()?, Val::Float64(1.5));
+
+        let huge = Column::new("huge", vec![Val::Int128(i128::MAX), Val::Int8(1)]);
+        assert!(matches!(huge.sum(), Err(Error::Overflow(_))));
+
+        let nulls = Column::new("nulls", vec![Val::Null, Val::Null]);
+        assert_eq!((nulls.sum()?, nulls.mean()?, nulls.median()?, nulls.min()?), (Val::Null, Val::Null, Val::Null, Val::Null));
+
+        let flags = Column::new("flags", vec![Val::Bool(true), Val::Null, Val::Bool(true)]);
+        assert_eq!(flags.sum()?, Val::Usize(2));
+        // unlike the earlier boolean-only sum, no values give null
+        assert_eq!(Column::new("flags", vec![Val::Null]).sum()?, Val::Null);
+
+        Ok(())
+    }
+
+    #[test]
+    fn mode_and_extremes() -> Result<(), Error> {
+        let df = df();
+
+        assert_eq!(df.mode("nationality")?, Val::from("Argentina"));
+        assert_eq!(df.mode("goals")?, Val::Int64(66));
+        assert_eq!(df.min("name")?, Val::from("C. Ronaldo"));
+        assert_eq!(df.max("nationality")?, Val::from("Uruguay"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{reduce, table::cell_text, DataFrame, Error, FormatOptions, Val};
+
+// How `pivot` combines the values that land in the same cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Agg {
+    Sum,
+    Mean,
+    Median,
+    Min,
+    Max,
+    // the number of non-null values
+    Count,
+    // the first non-null value
+    First,
+    // the last non-null value
+    Last,
+}
+
+impl Agg {
+    fn apply(self, vals: &[&Val]) -> Result<Val, Error> {
+        let mut present = vals.iter().filter(|val| !val.is_null());
+        match self {
+            Agg::Sum => reduce::sum(vals),
+            Agg::Mean => reduce::mean(vals),
+            Agg::Median => reduce::median(vals),
+            Agg::Min => reduce::min(vals),
+            Agg::Max => reduce::max(vals),
+            Agg::Count => Ok(Val::Usize(present.count())),
+            Agg::First => Ok(present.next().map_or(Val::Null, |&val| val.clone())),
+            Agg::Last => Ok(present.next_back().map_or(Val::Null, |&val| val.clone())),
+        }
+    }
+}
+
+impl DataFrame {
+    // Long to wide: one row per group of `index` values, in the order they
+    // first appear, and one column per distinct value of `columns`, sorted
+    // and named after the value, holding the `values` of that group and
+    // value. Cells with no value are null. Without `agg`, a cell with more
+    // than one value is an `Error::DuplicateEntry`, and a value named like
+    // another column, e.g. null next to the string "null", an
+    // `Error::DuplicateHeader`.
+    pub fn pivot(&self, index: &[&str], columns: &str, values: &str, agg: Option<Agg>) -> Result<DataFrame, Error> {
+        let index_cols = index.iter().map(|header| self.position(header)).collect::<Result<Vec<_>, Error>>()?;
+        let key_col = self.position(columns)?;
+        let value_col = self.position(values)?;
+
+        let text = |val: &Val| cell_text(val, &FormatOptions::default());
+        let mut keys = self.column_values(key_col).collect::<Vec<_>>();
+        keys.sort();
+        keys.dedup();
+
+        let mut headers = index.iter().map(|header| header.to_string()).collect::<Vec<_>>();
+        for key in &keys {
+            let header = text(key);
+            if headers.contains(&header) {
+                return Err(Error::DuplicateHeader(header));
+            }
+            headers.push(header);
+        }
+
+        let mut data = Vec::new();
+        let mut height = 0;
+        for rows in self.partitions(index)?.into_iter().filter(|rows| !rows.is_empty()) {
+            let mut cells = vec![Vec::new(); keys.len()];
+            for &row in &rows {
+                let key = &self.data[row * self.width + key_col];
+                let Ok(cell) = keys.binary_search(&key) else { continue };
+                cells[cell].push(&self.data[row * self.width + value_col]);
+            }
+
+            data.extend(index_cols.iter().map(|col| self.data[rows[0] * self.width + col].clone()));
+            for (key, vals) in keys.iter().zip(cells) {
+                let val = match (agg, vals.as_slice()) {
+                    (Some(agg), vals) => agg.apply(vals)?,
+                    (None, []) => Val::Null,
+                    (None, [val]) => (*val).clone(),
+                    (None, _) => {
+                        let entry = index
+                            .iter()
+                            .zip(&index_cols)
+                            .map(|(header, col)| format!("{header} = {}", text(&self.data[rows[0] * self.width + col])))
+                            .chain([format!("{columns} = {}", text(key))])
+                            .collect::<Vec<_>>();
+                        return Err(Error::DuplicateEntry(entry.join(", ")));
+                    },
+                };
+                data.push(val);
+            }
+            height += 1;
+        }
+
+        Ok(DataFrame::new(headers, data, index.len() + keys.len(), height))
+    }
+
+    // Wide to long: the `id_vars` columns, then a `variable` column naming
+    // each of `value_vars` and a `value` column with its values. Each of
+    // `value_vars` in turn gives one row per row of the frame. An empty
+    // `value_vars` melts every column not in `id_vars`. An id column named
+    // `variable` or `value` is an `Error::DuplicateHeader`.
+    pub fn melt(&self, id_vars: &[&str], value_vars: &[&str]) -> Result<DataFrame, Error> {
+        let id_cols = id_vars.iter().map(|header| self.position(header)).collect::<Result<Vec<_>, Error>>()?;
+        let value_cols = if value_vars.is_empty() {
+            (0..self.width).filter(|col| !id_cols.contains(col)).collect()
+        } else {
+            value_vars.iter().map(|header| self.position(header)).collect::<Result<Vec<_>, Error>>()?
+        };
+
+        let mut headers = Vec::with_capacity(id_vars.len() + 2);
+        for header in id_vars.iter().chain(&["variable", "value"]) {
+            if headers.iter().any(|h| h == header) {
+                return Err(Error::DuplicateHeader(header.to_string()));
+            }
+            headers.push(header.to_string());
+        }
+
+        let mut data = Vec::with_capacity(headers.len() * self.height * value_cols.len());
+        for &col in &value_cols {
+            let variable = Val::String(self.headers[col].clone());
+            for row in self.data.chunks(self.width.max(1)).take(self.height) {
+                data.extend(id_cols.iter().map(|&id| row[id].clone()));
+                data.push(variable.clone());
+                data.push(row[col].clone());
+            }
+        }
+
+        Ok(DataFrame::new(headers, data, id_vars.len() + 2, self.height * value_cols.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn df() -> DataFrame {
+        let csv = "name,season,goals
+Lionel Messi,2023,20
+Darwin Nunez,2023,13
+Lionel Messi,2022,30
+Darwin Nunez,2024,18
+";
+        DataFrame::read_str(csv.to_string()).unwrap()
+    }
+
+    #[test]
+    fn pivot_seasons() -> Result<(), Error> {
+        let wide = df().pivot(&["name"], "season", "goals", None)?;
+
+        assert_eq!(wide.headers(), &["name", "2022", "2023", "2024"]);
+        assert_eq!(wide.col("name"), Some(vec![&Val::from("Lionel Messi"), &Val::from("Darwin Nunez")]));
+        assert_eq!(wide.col("2022"), Some(vec![&Val::Int64(30), &Val::Null]));
+        assert_eq!(wide.col("2023"), Some(vec![&Val::Int64(20), &Val::Int64(13)]));
+
+        let totals = df().pivot(&[], "name", "goals", Some(Agg::Sum))?;
+        assert_eq!(totals.headers(), &["Darwin Nunez", "Lionel Messi"]);
+        assert_eq!(totals.col("Lionel Messi"), Some(vec![&Val::Int64(50)]));
+
+        let counts = df().pivot(&["season"], "name", "goals", Some(Agg::Count))?;
+        assert_eq!(counts.col("Darwin Nunez"), Some(vec![&Val::Usize(1), &Val::Usize(0), &Val::Usize(1)]));
+
+        let err = df().pivot(&[], "name", "goals", None);
+        assert!(matches!(err, Err(Error::DuplicateEntry(entry)) if entry == "name = Darwin Nunez"));
+        let mut twice = df();
+        twice.insert_column(crate::Column::new("season", vec![Val::Int64(2023); 4]))?;
+        let err = twice.pivot(&["name"], "season", "goals", None);
+        assert!(matches!(err, Err(Error::DuplicateEntry(entry)) if entry == "name = Lionel Messi, season = 2023"));
+        assert!(matches!(df().pivot(&["club"], "season", "goals", None), Err(Error::HeaderNotFound(_))));
+
+        let mut nulls = df();
+        nulls.insert_column(crate::Column::new("season", vec![Val::Null, Val::from("null"), Val::Null, Val::Null]))?;
+        assert!(matches!(nulls.pivot(&["name"], "season", "goals", Some(Agg::Sum)), Err(Error::DuplicateHeader(h)) if h == "null"));
+        let mut named = df();
+        named.insert_column(crate::Column::new("season", vec![Val::from("name"); 4]))?;
+        assert!(matches!(named.pivot(&["name"], "season", "goals", Some(Agg::Sum)), Err(Error::DuplicateHeader(h)) if h == "name"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn melt_and_back() -> Result<(), Error> {
+        let wide = df().pivot(&["name"], "season", "goals", None)?;
+        let long = wide.melt(&["name"], &[])?;
+
+        assert_eq!(long.headers(), &["name", "variable", "value"]);
+        assert_eq!(long.col("variable").map(|col| col.len()), Some(6));
+        assert_eq!(long.col("variable").map(|col| col[2].clone()), Some(Val::from("2023")));
+        assert_eq!(long.col("value").map(|col| col[2].clone()), Some(Val::Int64(20)));
+
+        let some = wide.melt(&[], &["2024"])?;
+        assert_eq!(some.headers(), &["variable", "value"]);
+        assert_eq!(some.col("value"), Some(vec![&Val::Null, &Val::Int64(18)]));
+        assert!(matches!(wide.melt(&["name"], &["2025"]), Err(Error::HeaderNotFound(_))));
+
+        let mut values = df();
+        values.insert_column(crate::Column::new("value", vec![Val::Int64(1); 4]))?;
+        assert!(matches!(values.melt(&["value"], &[]), Err(Error::DuplicateHeader(h)) if h == "value"));
+        assert!(matches!(df().melt(&["name", "name"], &["goals"]), Err(Error::DuplicateHeader(h)) if h == "name"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use std::ops::Index;
+
+use crate::{DataFrame, Error, Val};
+
+// A borrowed view of one row, its values in header order.
+#[derive(Debug, Clone, Copy)]
+pub struct Row<'a> {
+    headers: &'a [String],
+    values: &'a [Val],
+}
+
+impl<'a> Row<'a> {
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn headers(&self) -> &'a [String] {
+        self.headers
+    }
+
+    pub fn values(&self) -> &'a [Val] {
+        self.values
+    }
+
+    // the value under `header`, if the frame has such a column
+    pub fn val(&self, header: &str) -> Option<&'a Val> {
+        let col = self.headers.iter().position(|h| h == header)?;
+        Some(&self.values[col])
+    }
+
+    // the value under `header` converted into `T`
+    pub fn get<T: TryFrom<&'a Val, Error = Error>>(&self, header: &str) -> Result<T, Error> {
+        self.val(header).ok_or_else(|| Error::HeaderNotFound(header.to_string())).and_then(T::try_from)
+    }
+
+    // pairs of header and value, in header order
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, &'a Val)> + 'a {
+        self.headers.iter().map(String::as_str).zip(self.values)
+    }
+}
+
+impl<'a> Index<usize> for Row<'a> {
+    type Output = Val;
+
+    fn index(&self, col: usize) -> &Val {
+        &self.values[col]
+    }
+}
+
+impl<'a> Index<&str> for Row<'a> {
+    type Output = Val;
+
+    fn index(&self, header: &str) -> &Val {
+        self.val(header).unwrap_or_else(|| panic!("no column named {header:?}"))
+    }
+}
+
+impl DataFrame {
+    pub fn get_row(&self, idx: usize) -> Option<Row<'_>> {
+        (idx < self.height).then(|| Row {
+            headers: &self.headers,
+            values: &self.data[idx * self.width..(idx + 1) * self.width],
+        })
+    }
+
+    pub fn iter_rows(&self) -> impl ExactSizeIterator<Item = Row<'_>> + '_ {
+        (0..self.height).map(|idx| Row {
+            headers: &self.headers,
+            values: &self.data[idx * self.width..(idx + 1) * self.width],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn df() -> DataFrame {
+        DataFrame::read_str("name,nationality,goals\nLionel Messi,Argentina,66\nC. Ronaldo,Portugal,3\n".to_string()).unwrap()
+    }
+
+    #[test]
+    fn row_view() -> Result<(), Error> {
+        let df = df();
+        let row = df.get_row(1).ok_or(Error::IndexOutOfBounds { index: 1, len: df.height })?;
+
+        assert_eq!(row[0], Val::from("C. Ronaldo"));
+        assert_eq!(row["goals"], Val::Int64(3));
+        assert_eq!(row.get::<i64>("goals")?, 3);
+        assert_eq!(row.get::<String>("nationality")?, "Portugal");
+        assert!(matches!(row.get::<i64>("club"), Err(Error::HeaderNotFound(_))));
+        assert!(matches!(row.get::<bool>("goals"), Err(Error::IncompatibleConversion { .. })));
+        assert_eq!(row.iter().map(|(header, _)| header).collect::<Vec<_>>(), ["name", "nationality", "goals"]);
+        assert!(df.get_row(2).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn iterate_rows() -> Result<(), Error> {
+        let df = df();
+        let goals = df.iter_rows().map(|row| row.get::<i64>("goals")).sum::<Result<i64, Error>>()?;
+
+        assert_eq!(goals, 69);
+        assert_eq!(df.iter_rows().len(), 2);
+        assert_eq!(df.iter_rows().last().map(|row| row.values().len()), Some(3));
+
+        Ok(())
+    }
+}
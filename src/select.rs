@@ -0,0 +1,133 @@
+use crate::{DataFrame, Error};
+
+// SplitMix64, small and fast, and the same rows for a seed on every platform.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // uniform in `0..n`, rejecting the few draws that would bias it
+    fn below(&mut self, n: u64) -> u64 {
+        let threshold = n.wrapping_neg() % n;
+        loop {
+            let m = self.next() as u128 * n as u128;
+            if m as u64 >= threshold {
+                return (m >> 64) as u64;
+            }
+        }
+    }
+}
+
+impl DataFrame {
+    pub fn head(&self, n: usize) -> DataFrame {
+        self.slice(0, n)
+    }
+
+    pub fn tail(&self, n: usize) -> DataFrame {
+        self.slice(self.height.saturating_sub(n), n)
+    }
+
+    // Rows `offset..offset + len`, cut short at the end of the frame. Like
+    // `head` and `tail`, it returns a new frame holding a deep copy of every
+    // value in those rows, strings included.
+    pub fn slice(&self, offset: usize, len: usize) -> DataFrame {
+        let start = offset.min(self.height);
+        let end = offset.saturating_add(len).min(self.height);
+        let data = self.data[start * self.width..end * self.width].to_vec();
+        DataFrame::new(self.headers.clone(), data, self.width, end - start)
+    }
+
+    // the rows at `indices`, in that order and repeated when given twice
+    pub fn take(&self, indices: &[usize]) -> Result<DataFrame, Error> {
+        let mut data = Vec::with_capacity(indices.len() * self.width);
+        for &row in indices {
+            if row >= self.height {
+                return Err(Error::IndexOutOfBounds { index: row, len: self.height });
+            }
+            data.extend_from_slice(&self.data[row * self.width..(row + 1) * self.width]);
+        }
+        Ok(DataFrame::new(self.headers.clone(), data, self.width, indices.len()))
+    }
+
+    // `n` distinct rows picked at random, the same ones for the same seed
+    pub fn sample(&self, n: usize, seed: u64) -> Result<DataFrame, Error> {
+        if n > self.height {
+            return Err(Error::IndexOutOfBounds { index: n, len: self.height });
+        }
+        let mut rng = Rng(seed);
+        let mut rows = (0..self.height).collect::<Vec<_>>();
+        for i in 0..n {
+            let j = i + rng.below((self.height - i) as u64) as usize;
+            rows.swap(i, j);
+        }
+        self.take(&rows[..n])
+    }
+
+    // a `frac` share of the rows picked at random, rounded to the nearest row
+    pub fn sample_frac(&self, frac: f64, seed: u64) -> Result<DataFrame, Error> {
+        if !(0.0..=1.0).contains(&frac) {
+            return Err(Error::FractionOutOfRange(frac));
+        }
+        self.sample((frac * self.height as f64).round() as usize, seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Val;
+
+    fn df() -> DataFrame {
+        let csv = "name,goals\n".to_string() + &(0..10).map(|i| format!("player {i},{i}\n")).collect::<String>();
+        DataFrame::read_str(csv).unwrap()
+    }
+
+    fn goals(df: &DataFrame) -> Vec<i64> {
+        df.col("goals").unwrap().into_iter().map(|val| i64::try_from(val).unwrap()).collect()
+    }
+
+    #[test]
+    fn head_tail_slice() -> Result<(), Error> {
+        let df = df();
+
+        assert_eq!(goals(&df.head(3)), [0, 1, 2]);
+        assert_eq!(goals(&df.tail(2)), [8, 9]);
+        assert_eq!(goals(&df.head(20)).len(), 10);
+        assert_eq!(goals(&df.slice(4, 3)), [4, 5, 6]);
+        assert_eq!(goals(&df.slice(8, usize::MAX)), [8, 9]);
+        assert_eq!(df.slice(12, 3).headers(), df.headers());
+        assert!(goals(&df.slice(12, 3)).is_empty());
+
+        assert_eq!(goals(&df.take(&[7, 1, 7])?), [7, 1, 7]);
+        assert!(matches!(df.take(&[10]), Err(Error::IndexOutOfBounds { index: 10, len: 10 })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn seeded_sample() -> Result<(), Error> {
+        let df = df();
+        let sample = goals(&df.sample(5, 42)?);
+
+        assert_eq!(sample, goals(&df.sample(5, 42)?));
+        assert_ne!(sample, goals(&df.sample(5, 7)?));
+        let mut sorted = sample.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), 5);
+
+        assert_eq!(goals(&df.sample_frac(0.34, 1)?).len(), 3);
+        assert_eq!(df.sample(10, 3)?.col("name").map(|col| col.contains(&&Val::from("player 9"))), Some(true));
+        assert!(matches!(df.sample(11, 0), Err(Error::IndexOutOfBounds { index: 11, len: 10 })));
+        assert!(matches!(df.sample_frac(1.5, 0), Err(Error::FractionOutOfRange(f)) if f == 1.5));
+        assert!(matches!(df.sample_frac(f64::NAN, 0), Err(Error::FractionOutOfRange(_))));
+
+        Ok(())
+    }
+}
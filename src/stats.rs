@@ -0,0 +1,138 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{reduce, DataFrame, Interpolation, Val};
+
+pub(crate) fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+// the variance with `ddof` degrees of freedom taken off the count
+pub(crate) fn var(values: &[f64], ddof: usize) -> Option<f64> {
+    let mean = mean(values)?;
+    let n = values.len().checked_sub(ddof).filter(|&n| n > 0)?;
+    Some(values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n as f64)
+}
+
+// The most frequent value and how often it appears, the first to appear
+// winning a tie.
+pub(crate) fn mode<'a, I: IntoIterator<Item = &'a Val>>(values: I) -> Option<(&'a Val, usize)> {
+    let mut counts = HashMap::<&Val, (usize, usize)>::new();
+    for (i, val) in values.into_iter().enumerate() {
+        counts.entry(val).or_insert((0, i)).0 += 1;
+    }
+    counts
+        .into_iter()
+        .max_by(|(_, (c1, i1)), (_, (c2, i2))| c1.cmp(c2).then(i2.cmp(i1)))
+        .map(|(val, (count, _))| (val, count))
+}
+
+const STATISTICS: [&str; 12] = ["count", "null_count", "mean", "std", "min", "25%", "50%", "75%", "max", "unique", "top", "freq"];
+
+impl DataFrame {
+    // Summary statistics of every column, one row per statistic. Every
+    // column gets its count of values and of nulls. Columns of numbers also
+    // get the mean, standard deviation, extremes and quartiles as floats;
+    // other columns, including those mixing numbers with other values, get
+    // the count of distinct values and the most frequent one. Statistics
+    // that don't apply to a column are null, and the rows of either kind are
+    // left out when no column is of that kind.
+    pub fn describe(&self) -> DataFrame {
+        let mut columns = Vec::with_capacity(self.width);
+        let (mut numeric, mut other) = (false, false);
+        for col in 0..self.width {
+            let values = self.column_values(col).filter(|val| !val.is_null()).collect::<Vec<_>>();
+            let count = values.len();
+            let null_count = self.height - count;
+            let mut stats = vec![Val::Null; STATISTICS.len()];
+            stats[..2].clone_from_slice(&[Val::Usize(count), Val::Usize(null_count)]);
+
+            if self.is_numeric_column(col) {
+                numeric = true;
+                let mut floats = values.iter().filter_map(|val| val.as_f64()).collect::<Vec<_>>();
+                floats.sort_by(f64::total_cmp);
+                let float = |f: Option<f64>| f.map_or(Val::Null, Val::Float64);
+                let quantile = |q| reduce::quantile(&values, q, Interpolation::Linear).unwrap_or(Val::Null);
+                stats[2..9].clone_from_slice(&[
+                    float(mean(&floats)),
+                    float(var(&floats, 1).map(f64::sqrt)),
+                    float(floats.first().copied()),
+                    quantile(0.25),
+                    quantile(0.5),
+                    quantile(0.75),
+                    float(floats.last().copied()),
+                ]);
+            } else {
+                other = true;
+                let unique = values.iter().collect::<HashSet<_>>().len();
+                let (top, freq) = mode(values.iter().copied()).map_or((Val::Null, Val::Null), |(val, freq)| (val.clone(), Val::Usize(freq)));
+                stats[9..].clone_from_slice(&[Val::Usize(unique), top, freq]);
+            }
+            columns.push(stats);
+        }
+
+        let rows = (0..STATISTICS.len())
+            .filter(|&stat| match stat {
+                0..=1 => numeric || other,
+                2..=8 => numeric,
+                _ => other,
+            })
+            .collect::<Vec<_>>();
+
+        let mut headers = vec!["statistic".to_string()];
+        headers.extend(self.headers.iter().cloned());
+        let mut data = Vec::with_capacity(headers.len() * rows.len());
+        for &stat in &rows {
+            data.push(Val::from(STATISTICS[stat]));
+            data.extend(columns.iter().map(|stats| stats[stat].clone()));
+        }
+        DataFrame::new(headers, data, self.width + 1, rows.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[test]
+    fn helpers() {
+        let values = [1.0, 2.0, 3.0, 4.0];
+
+        assert_eq!(mean(&values), Some(2.5));
+        assert_eq!(var(&values, 0), Some(1.25));
+        assert_eq!(var(&[1.0], 1), None);
+    }
+
+    #[test]
+    fn describe_columns() -> Result<(), Error> {
+        let csv = "name,nationality,goals,xg\nLionel Messi,Argentina,66,1.5\nC. Ronaldo,Portugal,3,\nDarwin Nunez,Argentina,6969,2.5\nM. Balotelli,,888,x\n";
+        let df = DataFrame::read_str(csv.to_string())?;
+        let described = df.describe();
+        let stat = |column: &str, statistic: &str| {
+            let row = described.col("statistic")?.iter().position(|s| **s == Val::from(statistic))?;
+            described.col(column).map(|col| col[row].clone())
+        };
+
+        assert_eq!(described.headers(), &["statistic", "name", "nationality", "goals", "xg"]);
+        assert_eq!(described.col("statistic").map(|col| col.len()), Some(12));
+        assert_eq!(stat("goals", "count"), Some(Val::Usize(4)));
+        assert_eq!(stat("goals", "null_count"), Some(Val::Usize(0)));
+        assert_eq!(stat("goals", "mean"), Some(Val::Float64(1981.5)));
+        assert_eq!(stat("goals", "50%"), Some(Val::Float64(477.0)));
+        assert_eq!(stat("goals", "max"), Some(Val::Float64(6969.0)));
+        assert_eq!(stat("goals", "top"), Some(Val::Null));
+        assert_eq!(stat("nationality", "null_count"), Some(Val::Usize(1)));
+        assert_eq!(stat("nationality", "unique"), Some(Val::Usize(2)));
+        assert_eq!(stat("nationality", "top"), Some(Val::from("Argentina")));
+        assert_eq!(stat("nationality", "freq"), Some(Val::Usize(2)));
+        // numbers mixed with a string are described like strings
+        assert_eq!(stat("xg", "unique"), Some(Val::Usize(3)));
+        assert_eq!(stat("xg", "mean"), Some(Val::Null));
+
+        let numbers = DataFrame::read_str("goals\n1\n2\n".to_string())?.describe();
+        assert_eq!(numbers.col("statistic").map(|col| col.len()), Some(9));
+        assert!(numbers.to_string().contains("| std        | 0.7071067811865476 |"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,302 @@
+use std::fmt::{self, Write};
+
+use crate::{
+    unicode::{escape, grapheme_width, graphemes, width},
+    DataFrame, Val,
+};
+
+// How `DataFrame` is rendered as a text table, and exported to markdown,
+// html and latex. Exports write every row in full, the limits on rows and
+// widths only apply to the text table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    // rows shown at most, taller frames keeping their first and last rows
+    // around an elided middle
+    pub max_rows: usize,
+    // display columns a cell or header is cut to, ending with `…`
+    pub max_col_width: usize,
+    // a row with the dtype of each column under the header
+    pub show_dtypes: bool,
+    // a `shape: (height, width)` line under the table
+    pub show_shape: bool,
+    // digits after the point of floats, all of them when `None`
+    pub float_precision: Option<usize>,
+    // a first, unnamed column with the position of each row
+    pub show_index: bool,
+    // css classes of the `<table>` written by `to_html`
+    pub html_classes: Vec<String>,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            max_rows: 10,
+            max_col_width: 32,
+            show_dtypes: true,
+            show_shape: true,
+            float_precision: None,
+            show_index: false,
+            html_classes: Vec::new(),
+        }
+    }
+}
+
+// The text of a cell, strings unquoted and nulls spelt out.
+pub(crate) fn cell_text(val: &Val, options: &FormatOptions) -> String {
+    match (val, options.float_precision) {
+        (Val::Null, _) => "null".to_string(),
+        (Val::String(s), _) => s.clone(),
+        (Val::Categorical(c), _) => c.as_str().to_string(),
+        (Val::Float64(f), Some(precision)) => format!("{f:.precision$}"),
+        (Val::Float32(f), Some(precision)) => format!("{f:.precision$}"),
+        (val, _) => val.to_string(),
+    }
+}
+
+// `text` cut to `max` columns of display, ending with `…` when cut.
+pub(crate) fn truncate(text: &str, max: usize) -> String {
+    if width(text) <= max {
+        return text.to_string();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for grapheme in graphemes(text) {
+        used += grapheme_width(grapheme);
+        if used >= max {
+            break;
+        }
+        out.push_str(grapheme);
+    }
+    out.push('…');
+    out
+}
+
+// The rows to show, `None` standing for the elided ones.
+pub(crate) fn visible_rows(height: usize, max_rows: usize) -> Vec<Option<usize>> {
+    if height <= max_rows {
+        return (0..height).map(Some).collect();
+    }
+    let head = max_rows.div_ceil(2);
+    let tail = max_rows - head;
+    (0..head).map(Some).chain([None]).chain((height - tail..height).map(Some)).collect()
+}
+
+// A column as rendered: its header, dtype and whether it aligns right.
+pub(crate) struct GridColumn {
+    pub(crate) header: String,
+    pub(crate) dtype: String,
+    pub(crate) right: bool,
+}
+
+impl DataFrame {
+    // The dtype of the non-null values of a column, `null` when there are
+    // none and `mixed` when they differ.
+    pub(crate) fn dtype_label(&self, col: usize) -> String {
+        let mut dtypes = self.column_values(col).filter(|val| !val.is_null()).map(Val::dtype);
+        match dtypes.next() {
+            None => "null".to_string(),
+            Some(first) if dtypes.all(|dtype| dtype == first) => first.to_string(),
+            Some(_) => "mixed".to_string(),
+        }
+    }
+
+    // columns of numbers only are aligned to the right
+    pub(crate) fn is_numeric_column(&self, col: usize) -> bool {
+        let mut vals = self.column_values(col).filter(|val| !val.is_null()).peekable();
+        vals.peek().is_some() && vals.all(|val| val.dtype().is_numeric())
+    }
+
+    // the rendered columns, the index first when shown
+    pub(crate) fn grid_columns(&self, options: &FormatOptions) -> Vec<GridColumn> {
+        let index = GridColumn { header: String::new(), dtype: String::new(), right: true };
+        let columns = (0..self.width).map(|col| GridColumn {
+            header: self.headers[col].clone(),
+            dtype: self.dtype_label(col),
+            right: self.is_numeric_column(col),
+        });
+        options.show_index.then_some(index).into_iter().chain(columns).collect()
+    }
+
+    // the text of each cell of `row`, the index first when shown
+    pub(crate) fn grid_row(&self, row: usize, options: &FormatOptions) -> Vec<String> {
+        let cells = self.data[row * self.width..(row + 1) * self.width].iter().map(|val| cell_text(val, options));
+        options.show_index.then(|| row.to_string()).into_iter().chain(cells).collect()
+    }
+
+    pub fn to_string_with(&self, options: &FormatOptions) -> String {
+        let mut out = String::new();
+        let _ = self.write_table(options, &mut out);
+        out
+    }
+
+    fn write_table<W: Write>(&self, options: &FormatOptions, out: &mut W) -> fmt::Result {
+        let max = options.max_col_width.max(1);
+        let columns = self.grid_columns(options);
+
+        if self.width > 0 {
+            let headers = columns.iter().map(|c| truncate(&escape(&c.header), max)).collect::<Vec<_>>();
+            let dtypes = columns.iter().map(|c| truncate(&c.dtype, max)).collect::<Vec<_>>();
+            let right = columns.iter().map(|c| c.right).collect::<Vec<_>>();
+            let cells = visible_rows(self.height, options.max_rows)
+                .into_iter()
+                .map(|row| match row {
+                    Some(row) => self.grid_row(row, options).iter().map(|cell| truncate(&escape(cell), max)).collect(),
+                    None => vec!["…".to_string(); columns.len()],
+                })
+                .collect::<Vec<Vec<_>>>();
+
+            let mut widths = headers.iter().map(|header| width(header)).collect::<Vec<_>>();
+            for line in cells.iter().chain(options.show_dtypes.then_some(&dtypes)) {
+                line.iter().zip(&mut widths).for_each(|(cell, w)| *w = (*w).max(width(cell)));
+            }
+
+            let border = |out: &mut W, fill: char| -> fmt::Result {
+                widths.iter().try_for_each(|&w| write!(out, "+{}", fill.to_string().repeat(w + 2)))?;
+                writeln!(out, "+")
+            };
+            let line = |out: &mut W, line: &[String], right: &[bool]| -> fmt::Result {
+                for ((cell, &w), &right) in line.iter().zip(&widths).zip(right) {
+                    let pad = " ".repeat(w - width(cell));
+                    if right {
+                        write!(out, "| {pad}{cell} ")?;
+                    } else {
+                        write!(out, "| {cell}{pad} ")?;
+                    }
+                }
+                writeln!(out, "|")
+            };
+
+            let left = vec![false; columns.len()];
+            border(out, '-')?;
+            line(out, &headers, &left)?;
+            if options.show_dtypes {
+                line(out, &dtypes, &left)?;
+            }
+            border(out, '=')?;
+            cells.iter().try_for_each(|cells| line(out, cells, &right))?;
+            if !cells.is_empty() {
+                border(out, '-')?;
+            }
+        }
+
+        if options.show_shape {
+            writeln!(out, "shape: ({}, {})", self.height, self.width)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DataFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_table(&FormatOptions::default(), f)
+    }
+}
+
+impl fmt::Debug for DataFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    fn df() -> Result<DataFrame, Error> {
+        DataFrame::read_str("name,nationality,goals\nLionel Messi,Argentina,66\nC. Ronaldo,Portugal,3\nDarwin Nunez,,6969\n".to_string())
+    }
+
+    #[test]
+    fn render_table() -> Result<(), Error> {
+        let expected = "\
++--------------+-------------+-------+
+| name         | nationality | goals |
+| str          | str         | i64   |
++==============+=============+=======+
+| Lionel Messi | Argentina   |    66 |
+| C. Ronaldo   | Portugal    |     3 |
+| Darwin Nunez | null        |  6969 |
++--------------+-------------+-------+
+shape: (3, 3)
+";
+        assert_eq!(df()?.to_string(), expected);
+        assert_eq!(format!("{:?}", df()?), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn truncated() -> Result<(), Error> {
+        let options = FormatOptions { max_rows: 2, max_col_width: 6, show_dtypes: false, ..FormatOptions::default() };
+        let expected = "\
++--------+--------+-------+
+| name   | natio… | goals |
++========+========+=======+
+| Lione… | Argen… |    66 |
+| …      | …      |     … |
+| Darwi… | null   |  6969 |
++--------+--------+-------+
+shape: (3, 3)
+";
+        assert_eq!(df()?.to_string_with(&options), expected);
+        assert_eq!(visible_rows(5, 3), vec![Some(0), Some(1), None, Some(4)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn wide_and_control_characters() -> Result<(), Error> {
+        let mut df = DataFrame::default();
+        df.insert_column(crate::Column::new("name", vec![Val::from("Nuñez"), Val::from("孫興慜")]))?;
+        df.insert_column(crate::Column::new("note", vec![Val::from("two\nlines"), Val::Null]))?;
+        let options = FormatOptions { show_dtypes: false, show_shape: false, ..FormatOptions::default() };
+        let expected = "\
++--------+------------+
+| name   | note       |
++========+============+
+| Nuñez  | two\\nlines |
+| 孫興慜 | null       |
++--------+------------+
+";
+        assert_eq!(df.to_string_with(&options), expected);
+        assert_eq!(truncate("孫興慜", 4), "孫…");
+        assert_eq!(truncate("Nun\u{303}ez", 4), "Nun\u{303}…");
+
+        Ok(())
+    }
+
+    #[test]
+    fn precision_and_index() -> Result<(), Error> {
+        let df = DataFrame::read_str("name,xg\nLionel Messi,66.666\nC. Ronaldo,-0.8\n".to_string())?;
+        let options = FormatOptions { float_precision: Some(1), show_index: true, show_shape: false, ..FormatOptions::default() };
+        let expected = "\
++---+--------------+------+
+|   | name         | xg   |
+|   | str          | f64  |
++===+==============+======+
+| 0 | Lionel Messi | 66.7 |
+| 1 | C. Ronaldo   | -0.8 |
++---+--------------+------+
+";
+        assert_eq!(df.to_string_with(&options), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn empty_frames() -> Result<(), Error> {
+        assert_eq!(DataFrame::default().to_string(), "shape: (0, 0)\n");
+
+        let df = DataFrame::read_str("a very long header,x\n".to_string())?;
+        assert_eq!(df.to_string(), "\
++--------------------+------+
+| a very long header | x    |
+| null               | null |
++====================+======+
+shape: (0, 2)
+");
+
+        Ok(())
+    }
+}
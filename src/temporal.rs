@@ -0,0 +1,581 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use crate::Error;
+
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+const NANOS_PER_MINUTE: i64 = 60 * NANOS_PER_SECOND;
+const NANOS_PER_HOUR: i64 = 60 * NANOS_PER_MINUTE;
+const NANOS_PER_DAY: i64 = 24 * NANOS_PER_HOUR;
+
+// The formats tried when no others are given, see `CsvOptions`.
+pub(crate) const DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f%z",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f%z",
+    "%Y-%m-%d %H:%M:%S%.f",
+];
+pub(crate) const DATE_FORMATS: &[&str] = &["%Y-%m-%d"];
+pub(crate) const TIME_FORMATS: &[&str] = &["%H:%M:%S%.f", "%H:%M"];
+
+const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+
+// A calendar date, stored as days since 1970-01-01.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    days: i32,
+}
+
+// A time of day with nanosecond precision.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    nanos: u64,
+}
+
+// A date and time, with an optional offset from UTC in seconds. Datetimes
+// order by the instant they describe, naive ones as if they were in UTC.
+#[derive(Clone, Copy)]
+pub struct Datetime {
+    date: Date,
+    time: Time,
+    offset: Option<i32>,
+}
+
+// A signed span of time with nanosecond precision.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Duration {
+    nanos: i64,
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Date {
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        // days from civil, counting years from March so leap days come last
+        let year = year as i64 - (month <= 2) as i64;
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let doy = (153 * ((month as i64 + 9) % 12) + 2) / 5 + day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        let days = era * 146097 + doe - 719468;
+        days.try_into().ok().map(|days| Self { days })
+    }
+
+    pub fn parse_from_str(input: &str, format: &str) -> Option<Self> {
+        let parsed = Parsed::parse(input, format)?;
+        parsed.date()
+    }
+
+    // year, month and day
+    fn civil(self) -> (i32, u32, u32) {
+        let days = self.days as i64 + 719468;
+        let era = days.div_euclid(146097);
+        let doe = days.rem_euclid(146097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + (month <= 2) as i64) as i32;
+        (year, month, day)
+    }
+
+    pub fn year(&self) -> i32 {
+        self.civil().0
+    }
+
+    pub fn month(&self) -> u32 {
+        self.civil().1
+    }
+
+    pub fn day(&self) -> u32 {
+        self.civil().2
+    }
+
+    // ISO weekday, from 1 for Monday to 7 for Sunday
+    pub fn weekday(&self) -> u32 {
+        (self.days as i64 + 3).rem_euclid(7) as u32 + 1
+    }
+
+    pub fn checked_add_days(&self, days: i64) -> Option<Self> {
+        let days = (self.days as i64).checked_add(days)?;
+        days.try_into().ok().map(|days| Self { days })
+    }
+
+    pub fn duration_since(&self, earlier: Date) -> Option<Duration> {
+        Duration::from_days(self.days as i64 - earlier.days as i64)
+    }
+
+    fn nanos(self) -> i128 {
+        self.days as i128 * NANOS_PER_DAY as i128
+    }
+}
+
+impl Time {
+    pub fn new(hour: u32, minute: u32, second: u32, nano: u32) -> Option<Self> {
+        if hour > 23 || minute > 59 || second > 59 || nano as i64 >= NANOS_PER_SECOND {
+            return None;
+        }
+        let nanos = hour as i64 * NANOS_PER_HOUR + minute as i64 * NANOS_PER_MINUTE + second as i64 * NANOS_PER_SECOND + nano as i64;
+        Some(Self { nanos: nanos as u64 })
+    }
+
+    pub fn parse_from_str(input: &str, format: &str) -> Option<Self> {
+        let parsed = Parsed::parse(input, format)?;
+        parsed.time()
+    }
+
+    pub fn hour(&self) -> u32 {
+        (self.nanos as i64 / NANOS_PER_HOUR) as u32
+    }
+
+    pub fn minute(&self) -> u32 {
+        (self.nanos as i64 % NANOS_PER_HOUR / NANOS_PER_MINUTE) as u32
+    }
+
+    pub fn second(&self) -> u32 {
+        (self.nanos as i64 % NANOS_PER_MINUTE / NANOS_PER_SECOND) as u32
+    }
+
+    pub fn nanosecond(&self) -> u32 {
+        (self.nanos as i64 % NANOS_PER_SECOND) as u32
+    }
+
+    // adds a duration, wrapping around midnight
+    pub fn wrapping_add(&self, duration: Duration) -> Self {
+        let nanos = (self.nanos as i128 + duration.nanos as i128).rem_euclid(NANOS_PER_DAY as i128);
+        Self { nanos: nanos as u64 }
+    }
+
+    pub fn wrapping_sub(&self, duration: Duration) -> Self {
+        let nanos = (self.nanos as i128 - duration.nanos as i128).rem_euclid(NANOS_PER_DAY as i128);
+        Self { nanos: nanos as u64 }
+    }
+
+    pub fn duration_since(&self, earlier: Time) -> Duration {
+        Duration { nanos: self.nanos as i64 - earlier.nanos as i64 }
+    }
+}
+
+impl Datetime {
+    pub fn new(date: Date, time: Time) -> Self {
+        Self { date, time, offset: None }
+    }
+
+    // the same wall clock time at an offset from UTC, within a day
+    pub fn with_offset(self, seconds: i32) -> Option<Self> {
+        if seconds.unsigned_abs() >= 86400 {
+            return None;
+        }
+        Some(Self { offset: Some(seconds), ..self })
+    }
+
+    pub fn parse_from_str(input: &str, format: &str) -> Option<Self> {
+        let parsed = Parsed::parse(input, format)?;
+        let datetime = Self::new(parsed.date()?, parsed.time()?);
+        match parsed.offset {
+            Some(offset) => datetime.with_offset(offset),
+            None => Some(datetime),
+        }
+    }
+
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    pub fn time(&self) -> Time {
+        self.time
+    }
+
+    pub fn offset(&self) -> Option<i32> {
+        self.offset
+    }
+
+    fn local_nanos(self) -> i128 {
+        self.date.nanos() + self.time.nanos as i128
+    }
+
+    fn utc_nanos(self) -> i128 {
+        self.local_nanos() - self.offset.unwrap_or(0) as i128 * NANOS_PER_SECOND as i128
+    }
+
+    fn from_local_nanos(nanos: i128, offset: Option<i32>) -> Option<Self> {
+        let days = nanos.div_euclid(NANOS_PER_DAY as i128);
+        let time = Time { nanos: nanos.rem_euclid(NANOS_PER_DAY as i128) as u64 };
+        let date = Date { days: days.try_into().ok()? };
+        Some(Self { date, time, offset })
+    }
+
+    pub fn checked_add(&self, duration: Duration) -> Option<Self> {
+        Self::from_local_nanos(self.local_nanos() + duration.nanos as i128, self.offset)
+    }
+
+    pub fn checked_sub(&self, duration: Duration) -> Option<Self> {
+        Self::from_local_nanos(self.local_nanos() - duration.nanos as i128, self.offset)
+    }
+
+    // the time elapsed between two instants, naive datetimes count as UTC
+    pub fn duration_since(&self, earlier: Datetime) -> Option<Duration> {
+        let nanos = self.utc_nanos() - earlier.utc_nanos();
+        nanos.try_into().ok().map(|nanos| Duration { nanos })
+    }
+}
+
+impl PartialEq for Datetime {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Datetime {}
+
+impl Hash for Datetime {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.utc_nanos(), self.offset).hash(state)
+    }
+}
+
+impl PartialOrd for Datetime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Datetime {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.utc_nanos()
+            .cmp(&other.utc_nanos())
+            .then(self.offset.cmp(&other.offset))
+    }
+}
+
+impl Duration {
+    pub fn from_days(days: i64) -> Option<Self> {
+        days.checked_mul(NANOS_PER_DAY).map(Self::from_nanos)
+    }
+
+    pub fn from_hours(hours: i64) -> Option<Self> {
+        hours.checked_mul(NANOS_PER_HOUR).map(Self::from_nanos)
+    }
+
+    pub fn from_minutes(minutes: i64) -> Option<Self> {
+        minutes.checked_mul(NANOS_PER_MINUTE).map(Self::from_nanos)
+    }
+
+    pub fn from_seconds(seconds: i64) -> Option<Self> {
+        seconds.checked_mul(NANOS_PER_SECOND).map(Self::from_nanos)
+    }
+
+    pub fn from_nanos(nanos: i64) -> Self {
+        Self { nanos }
+    }
+
+    pub fn num_days(&self) -> i64 {
+        self.nanos / NANOS_PER_DAY
+    }
+
+    pub fn num_seconds(&self) -> i64 {
+        self.nanos / NANOS_PER_SECOND
+    }
+
+    pub fn num_nanos(&self) -> i64 {
+        self.nanos
+    }
+
+    // the number of days, if the duration is a whole number of them
+    pub fn whole_days(&self) -> Option<i64> {
+        (self.nanos % NANOS_PER_DAY == 0).then_some(self.nanos / NANOS_PER_DAY)
+    }
+
+    pub fn checked_add(&self, other: Duration) -> Option<Self> {
+        self.nanos.checked_add(other.nanos).map(Self::from_nanos)
+    }
+
+    pub fn checked_sub(&self, other: Duration) -> Option<Self> {
+        self.nanos.checked_sub(other.nanos).map(Self::from_nanos)
+    }
+
+    pub fn checked_mul(&self, n: i64) -> Option<Self> {
+        self.nanos.checked_mul(n).map(Self::from_nanos)
+    }
+
+    pub fn checked_div(&self, n: i64) -> Option<Self> {
+        self.nanos.checked_div(n).map(Self::from_nanos)
+    }
+
+    pub fn checked_neg(&self) -> Option<Self> {
+        self.nanos.checked_neg().map(Self::from_nanos)
+    }
+}
+
+// The fields found in an input while matching it against a format string.
+#[derive(Default)]
+struct Parsed {
+    year: Option<i32>,
+    month: Option<u32>,
+    day: Option<u32>,
+    hour: Option<u32>,
+    minute: Option<u32>,
+    second: Option<u32>,
+    nano: u32,
+    offset: Option<i32>,
+}
+
+// Takes between `min` and `max` leading ascii digits off `input`.
+fn digits(input: &mut &str, min: usize, max: usize) -> Option<u32> {
+    let len = input.bytes().take(max).take_while(u8::is_ascii_digit).count();
+    if len < min {
+        return None;
+    }
+    let (number, rest) = input.split_at(len);
+    *input = rest;
+    number.parse().ok()
+}
+
+impl Parsed {
+    // Supported specifiers are `%Y` (four digit year), `%y` (two digit year
+    // from 1970 to 2069), `%m`, `%d`, `%H`, `%M`, `%S`, `%b` (month name like
+    // `May`), `%.f` (an optional fraction of a second like `.25`), `%z` (an
+    // offset like `Z`, `+02:00` or `-0530`) and `%%`.
+    fn parse(input: &str, format: &str) -> Option<Self> {
+        let mut parsed = Parsed::default();
+        let mut input = input;
+        let mut format = format.chars();
+
+        while let Some(c) = format.next() {
+            if c != '%' {
+                input = input.strip_prefix(c)?;
+                continue;
+            }
+            match format.next()? {
+                'Y' => {
+                    let negative = input.starts_with('-');
+                    if negative {
+                        input = &input[1..];
+                    }
+                    let year = digits(&mut input, 4, 4)? as i32;
+                    parsed.year = Some(if negative { -year } else { year });
+                },
+                'y' => {
+                    let year = digits(&mut input, 2, 2)? as i32;
+                    parsed.year = Some(if year < 70 { 2000 + year } else { 1900 + year });
+                },
+                'm' => parsed.month = Some(digits(&mut input, 1, 2)?),
+                'd' => parsed.day = Some(digits(&mut input, 1, 2)?),
+                'H' => parsed.hour = Some(digits(&mut input, 1, 2)?),
+                'M' => parsed.minute = Some(digits(&mut input, 2, 2)?),
+                'S' => parsed.second = Some(digits(&mut input, 2, 2)?),
+                'b' => {
+                    let name = input.get(..3)?.to_ascii_lowercase();
+                    let month = MONTHS.iter().position(|m| *m == name)?;
+                    parsed.month = Some(month as u32 + 1);
+                    input = &input[3..];
+                },
+                '.' => {
+                    if format.next()? != 'f' {
+                        return None;
+                    }
+                    if let Some(rest) = input.strip_prefix('.') {
+                        let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+                        if len == 0 || len > 9 {
+                            return None;
+                        }
+                        parsed.nano = rest[..len].parse::<u32>().ok()? * 10u32.pow(9 - len as u32);
+                        input = &rest[len..];
+                    }
+                },
+                'z' => {
+                    if let Some(rest) = input.strip_prefix('Z') {
+                        parsed.offset = Some(0);
+                        input = rest;
+                        continue;
+                    }
+                    let sign = match input.chars().next()? {
+                        '+' => 1,
+                        '-' => -1,
+                        _ => return None,
+                    };
+                    input = &input[1..];
+                    let hours = digits(&mut input, 2, 2)?;
+                    if let Some(rest) = input.strip_prefix(':') {
+                        input = rest;
+                    }
+                    let minutes = if input.starts_with(|c: char| c.is_ascii_digit()) {
+                        digits(&mut input, 2, 2)?
+                    } else {
+                        0
+                    };
+                    parsed.offset = Some(sign * (hours * 3600 + minutes * 60) as i32);
+                },
+                '%' => input = input.strip_prefix('%')?,
+                _ => return None,
+            }
+        }
+
+        input.is_empty().then_some(parsed)
+    }
+
+    fn date(&self) -> Option<Date> {
+        Date::new(self.year?, self.month?, self.day?)
+    }
+
+    fn time(&self) -> Option<Time> {
+        Time::new(self.hour?, self.minute?, self.second.unwrap_or(0), self.nano)
+    }
+}
+
+fn fraction(f: &mut fmt::Formatter<'_>, nano: u32) -> fmt::Result {
+    if nano == 0 {
+        return Ok(());
+    }
+    let digits = format!("{:09}", nano);
+    write!(f, ".{}", digits.trim_end_matches('0'))
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.civil();
+        if year < 0 {
+            write!(f, "-{:04}-{:02}-{:02}", -year, month, day)
+        } else {
+            write!(f, "{:04}-{:02}-{:02}", year, month, day)
+        }
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour(), self.minute(), self.second())?;
+        fraction(f, self.nanosecond())
+    }
+}
+
+impl fmt::Display for Datetime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}T{}", self.date, self.time)?;
+        match self.offset {
+            Some(offset) => {
+                let sign = if offset < 0 { '-' } else { '+' };
+                let offset = offset.unsigned_abs();
+                write!(f, "{}{:02}:{:02}", sign, offset / 3600, offset % 3600 / 60)
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+// Written as `[-][<days>d ]HH:MM:SS[.fraction]`, e.g. `1d 02:30:00`.
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.nanos < 0 {
+            f.write_str("-")?;
+        }
+        let nanos = self.nanos.unsigned_abs();
+        let days = nanos / NANOS_PER_DAY as u64;
+        if days > 0 {
+            write!(f, "{}d ", days)?;
+        }
+        let rest = Time { nanos: nanos % NANOS_PER_DAY as u64 };
+        write!(f, "{}", rest)
+    }
+}
+
+macro_rules! impl_debug_from_str {
+    ($($ty:ident, $formats:expr;)*) => {
+        $(
+            impl fmt::Debug for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "{}({})", stringify!($ty), self)
+                }
+            }
+
+            // Parses the iso formats read from csv by default.
+            impl FromStr for $ty {
+                type Err = Error;
+                fn from_str(input: &str) -> Result<Self, Self::Err> {
+                    $formats
+                        .iter()
+                        .find_map(|format| Self::parse_from_str(input, format))
+                        .ok_or_else(|| Error::ValParseError(input.to_string()))
+                }
+            }
+        )*
+    };
+}
+
+impl_debug_from_str! {
+    Date, DATE_FORMATS;
+    Time, TIME_FORMATS;
+    Datetime, DATETIME_FORMATS;
+}
+
+impl fmt::Debug for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Duration({})", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calendar() {
+        let date = Date::new(2024, 5, 12).unwrap();
+
+        assert_eq!((date.year(), date.month(), date.day(), date.weekday()), (2024, 5, 12, 7));
+        assert_eq!(Date::new(1970, 1, 1).map(|d| d.days), Some(0));
+        assert_eq!(Date::new(2000, 3, 1).and_then(|d| d.checked_add_days(-1)), Date::new(2000, 2, 29));
+        assert_eq!(Date::new(1600, 2, 29).map(|d| d.to_string()), Some("1600-02-29".to_string()));
+        assert!(Date::new(2023, 2, 29).is_none());
+        assert!(Date::new(2023, 13, 1).is_none());
+    }
+
+    #[test]
+    fn parse_formats() -> Result<(), Error> {
+        assert_eq!("2024-05-12".parse::<Date>()?, Date::new(2024, 5, 12).unwrap());
+        assert_eq!(Date::parse_from_str("12/5/24", "%d/%m/%y"), Date::new(2024, 5, 12));
+        assert_eq!(Date::parse_from_str("12 may 2024", "%d %b %Y"), Date::new(2024, 5, 12));
+        assert_eq!("21:45".parse::<Time>()?, Time::new(21, 45, 0, 0).unwrap());
+        assert_eq!("21:45:30.25".parse::<Time>()?.nanosecond(), 250_000_000);
+
+        let datetime = "2024-05-12T21:45:00+02:00".parse::<Datetime>()?;
+        assert_eq!(datetime.offset(), Some(7200));
+        assert_eq!(datetime.to_string(), "2024-05-12T21:45:00+02:00");
+        assert_eq!(datetime, "2024-05-12 19:45:00Z".parse::<Datetime>()?.with_offset(7200).and_then(|d| d.checked_add(Duration::from_hours(2)?)).unwrap());
+        assert!(datetime > "2024-05-12T19:00:00Z".parse::<Datetime>()?);
+
+        assert!("2024-05-12T25:00:00".parse::<Datetime>().is_err());
+        assert!(Date::parse_from_str("2024-05-12 ", "%Y-%m-%d").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn durations() -> Result<(), Error> {
+        let kickoff = "2024-05-12T20:00:00Z".parse::<Datetime>()?;
+        let final_whistle = "2024-05-12T23:50:00+01:00".parse::<Datetime>()?;
+        let played = final_whistle.duration_since(kickoff).unwrap();
+
+        assert_eq!(played, Duration::from_minutes(170).unwrap());
+        assert_eq!(played.to_string(), "02:50:00");
+        assert_eq!(Duration::from_seconds(-90061).map(|d| d.to_string()), Some("-1d 01:01:01".to_string()));
+        assert_eq!("23:30:00".parse::<Time>()?.wrapping_add(Duration::from_hours(1).unwrap()), "00:30:00".parse::<Time>()?);
+
+        Ok(())
+    }
+}
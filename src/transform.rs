@@ -0,0 +1,245 @@
+use crate::{
+    reduce::{number, widen},
+    Column, DataFrame, Error, Val,
+};
+
+// How `rank` numbers equal values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RankMethod {
+    // the mean of the ranks they span, as a float
+    #[default]
+    Average,
+    // the lowest rank they span
+    Min,
+    // the highest rank they span
+    Max,
+    // the lowest rank, ranks following on without gaps
+    Dense,
+    // distinct ranks in the order the values appear
+    Ordinal,
+}
+
+// A running reduction, which nulls leave as it was and are kept as null.
+// Numbers are widened like for `sum` when `numeric`, other values are
+// taken as they are.
+fn accumulate<F: Fn(&Val, &Val) -> Result<Val, Error>>(vals: &[&Val], op: &str, numeric: bool, f: F) -> Result<Vec<Val>, Error> {
+    let mut acc = Val::Null;
+    vals.iter()
+        .map(|&val| {
+            if val.is_null() {
+                return Ok(Val::Null);
+            }
+            let val = if numeric { widen(number(val, op)?) } else { val.clone() };
+            acc = if acc.is_null() { val } else { f(&acc, &val)? };
+            Ok(acc.clone())
+        })
+        .collect()
+}
+
+// The value `n` positions before each one, after it when negative.
+fn lagged<'a>(vals: &[&'a Val], pos: usize, n: isize) -> Option<&'a Val> {
+    let from = pos.checked_add_signed(n.checked_neg()?)?;
+    vals.get(from).copied()
+}
+
+// Unsigned and narrow integers become signed integers wide enough for the
+// difference of any two of them.
+fn signed(val: &Val) -> Result<Val, Error> {
+    let val = match *val {
+        Val::Int8(n) => Val::Int64(n as i64),
+        Val::Int16(n) => Val::Int64(n as i64),
+        Val::Int32(n) => Val::Int64(n as i64),
+        Val::Uint8(n) => Val::Int64(n as i64),
+        Val::Uint16(n) => Val::Int64(n as i64),
+        Val::Uint32(n) => Val::Int64(n as i64),
+        Val::Int64(n) => Val::Int128(n as i128),
+        Val::Uint64(n) => Val::Int128(n as i128),
+        Val::Isize(n) => Val::Int128(n as i128),
+        Val::Usize(n) => Val::Int128(n as i128),
+        Val::UInt128(n) => Val::Int128(n.try_into().map_err(|_| Error::Overflow(format!("{n} as i128")))?),
+        ref val => val.clone(),
+    };
+    Ok(val)
+}
+
+fn rank(vals: &[&Val], method: RankMethod) -> Vec<Val> {
+    let mut order = (0..vals.len()).filter(|&i| !vals[i].is_null()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| vals[a].cmp(vals[b]));
+
+    let mut ranks = vec![Val::Null; vals.len()];
+    let (mut first, mut dense) = (1, 0);
+    for ties in order.chunk_by(|&a, &b| vals[a] == vals[b]) {
+        let last = first + ties.len() - 1;
+        dense += 1;
+        for (i, &row) in ties.iter().enumerate() {
+            ranks[row] = match method {
+                RankMethod::Average => Val::Float64((first + last) as f64 / 2.0),
+                RankMethod::Min => Val::Usize(first),
+                RankMethod::Max => Val::Usize(last),
+                RankMethod::Dense => Val::Usize(dense),
+                RankMethod::Ordinal => Val::Usize(first + i),
+            };
+        }
+        first = last + 1;
+    }
+    ranks
+}
+
+impl DataFrame {
+    // Applies `f` to the values of `column` within each partition, and puts
+    // the values it gives back in the rows they came from.
+    fn over<F>(&self, column: &str, partition_by: &[&str], mut f: F) -> Result<Column, Error>
+    where
+        F: FnMut(&[&Val]) -> Result<Vec<Val>, Error>,
+    {
+        let col = self.position(column)?;
+        let mut values = vec![Val::Null; self.height];
+        for rows in self.partitions(partition_by)? {
+            let vals = rows.iter().map(|row| &self.data[row * self.width + col]).collect::<Vec<_>>();
+            rows.iter().zip(f(&vals)?).for_each(|(&row, val)| values[row] = val);
+        }
+        Ok(Column::new(column, values))
+    }
+
+    // The running sum of the numbers of `column`, restarting for each group
+    // of `partition_by`. Integers are summed in 64 bits at least.
+    pub fn cumsum(&self, column: &str, partition_by: &[&str]) -> Result<Column, Error> {
+        self.over(column, partition_by, |vals| accumulate(vals, "cumsum", true, |acc, val| acc + val))
+    }
+
+    pub fn cumprod(&self, column: &str, partition_by: &[&str]) -> Result<Column, Error> {
+        self.over(column, partition_by, |vals| accumulate(vals, "cumprod", true, |acc, val| acc * val))
+    }
+
+    // the running minimum, of any values ordered like `Val` is
+    pub fn cummin(&self, column: &str, partition_by: &[&str]) -> Result<Column, Error> {
+        self.over(column, partition_by, |vals| accumulate(vals, "cummin", false, |acc, val| Ok(acc.min(val).clone())))
+    }
+
+    pub fn cummax(&self, column: &str, partition_by: &[&str]) -> Result<Column, Error> {
+        self.over(column, partition_by, |vals| accumulate(vals, "cummax", false, |acc, val| Ok(acc.max(val).clone())))
+    }
+
+    // The value `n` rows before each one, or after it when `n` is negative,
+    // `fill` standing in past the ends.
+    pub fn shift(&self, column: &str, n: isize, fill: Val, partition_by: &[&str]) -> Result<Column, Error> {
+        self.over(column, partition_by, |vals| {
+            Ok((0..vals.len()).map(|pos| lagged(vals, pos, n).map_or_else(|| fill.clone(), Val::clone)).collect())
+        })
+    }
+
+    // Each value minus the one `n` rows before it, null past the start or
+    // next to a null. Integers are widened to signed ones first, so the
+    // difference neither wraps nor underflows.
+    pub fn diff(&self, column: &str, n: isize, partition_by: &[&str]) -> Result<Column, Error> {
+        self.over(column, partition_by, |vals| {
+            (0..vals.len())
+                .map(|pos| match (vals[pos], lagged(vals, pos, n)) {
+                    (Val::Null, _) | (_, None | Some(Val::Null)) => Ok(Val::Null),
+                    (val, Some(prev)) => signed(val)? - signed(prev)?,
+                })
+                .collect()
+        })
+    }
+
+    // The relative change from the value `n` rows before, as a float. It's
+    // null when that value is 0, there being no change relative to nothing.
+    pub fn pct_change(&self, column: &str, n: isize, partition_by: &[&str]) -> Result<Column, Error> {
+        self.over(column, partition_by, |vals| {
+            (0..vals.len())
+                .map(|pos| match (vals[pos], lagged(vals, pos, n)) {
+                    (Val::Null, _) | (_, None | Some(Val::Null)) => Ok(Val::Null),
+                    (val, Some(prev)) => {
+                        let val = number(val, "pct_change")?.as_f64().unwrap_or(f64::NAN);
+                        let prev = number(prev, "pct_change")?.as_f64().unwrap_or(f64::NAN);
+                        Ok(if prev == 0.0 { Val::Null } else { Val::Float64(val / prev - 1.0) })
+                    },
+                })
+                .collect()
+        })
+    }
+
+    // The position of each value among the sorted values of `column`,
+    // starting from 1, nulls staying null.
+    pub fn rank(&self, column: &str, method: RankMethod, partition_by: &[&str]) -> Result<Column, Error> {
+        self.over(column, partition_by, |vals| Ok(rank(vals, method)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn df() -> DataFrame {
+        let csv = "name,goals
+Lionel Messi,1
+Darwin Nunez,4
+Lionel Messi,2
+Lionel Messi,
+Darwin Nunez,0
+Lionel Messi,2
+";
+        DataFrame::read_str(csv.to_string()).unwrap()
+    }
+
+    fn ints(column: Column) -> Vec<Option<i128>> {
+        column.iter().map(|val| val.as_f64().map(|f| f as i128)).collect()
+    }
+
+    #[test]
+    fn cumulative() -> Result<(), Error> {
+        let df = df();
+
+        assert_eq!(ints(df.cumsum("goals", &[])?), [Some(1), Some(5), Some(7), None, Some(7), Some(9)]);
+        assert_eq!(ints(df.cumsum("goals", &["name"])?), [Some(1), Some(4), Some(3), None, Some(4), Some(5)]);
+        assert_eq!(ints(df.cumprod("goals", &["name"])?), [Some(1), Some(4), Some(2), None, Some(0), Some(4)]);
+        assert_eq!(ints(df.cummin("goals", &[])?), [Some(1), Some(1), Some(1), None, Some(0), Some(0)]);
+        assert_eq!(ints(df.cummax("goals", &["name"])?), [Some(1), Some(4), Some(2), None, Some(4), Some(2)]);
+        assert!(matches!(df.cumsum("name", &[]), Err(Error::UnsupportedOperation(_))));
+
+        let mut bytes = DataFrame::default();
+        bytes.insert_column(Column::new("b", vec![Val::Uint8(200), Val::Uint8(200)]))?;
+        assert_eq!(bytes.cumsum("b", &[])?.values(), &[Val::Uint64(200), Val::Uint64(400)]);
+        assert_eq!(bytes.cumprod("b", &[])?.values()[1], Val::Uint64(40000));
+        assert_eq!(bytes.diff("b", -1, &[])?.values(), &[Val::Int64(0), Val::Null]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn shift_and_diff() -> Result<(), Error> {
+        let df = df();
+
+        assert_eq!(ints(df.shift("goals", 1, Val::Int64(0), &[])?), [Some(0), Some(1), Some(4), Some(2), None, Some(0)]);
+        assert_eq!(ints(df.shift("goals", -1, Val::Null, &["name"])?), [Some(2), Some(0), None, Some(2), None, None]);
+        assert_eq!(ints(df.diff("goals", 1, &["name"])?), [None, None, Some(1), None, Some(-4), None]);
+        assert_eq!(df.diff("goals", 1, &[])?.values()[1], Val::Int128(3));
+
+        let pct = df.pct_change("goals", 1, &["name"])?;
+        assert_eq!(pct.values()[2], Val::Float64(1.0));
+        assert_eq!(pct.values()[4], Val::Float64(-1.0));
+        assert!(pct.values()[5].is_null());
+
+        let pct = df.pct_change("goals", 1, &[])?;
+        assert_eq!(pct.values()[1], Val::Float64(3.0));
+        assert!(pct.values()[5].is_null());
+
+        Ok(())
+    }
+
+    #[test]
+    fn ranks() -> Result<(), Error> {
+        let df = df();
+        let rank = |method| df.rank("goals", method, &[]).map(|col| col.into_values());
+
+        assert_eq!(rank(RankMethod::Average)?[2], Val::Float64(3.5));
+        assert_eq!(ints(df.rank("goals", RankMethod::Min, &[])?), [Some(2), Some(5), Some(3), None, Some(1), Some(3)]);
+        assert_eq!(ints(df.rank("goals", RankMethod::Max, &[])?), [Some(2), Some(5), Some(4), None, Some(1), Some(4)]);
+        assert_eq!(ints(df.rank("goals", RankMethod::Dense, &[])?), [Some(2), Some(4), Some(3), None, Some(1), Some(3)]);
+        assert_eq!(ints(df.rank("goals", RankMethod::Ordinal, &[])?), [Some(2), Some(5), Some(3), None, Some(1), Some(4)]);
+        assert_eq!(ints(df.rank("goals", RankMethod::Ordinal, &["name"])?), [Some(1), Some(2), Some(2), None, Some(1), Some(3)]);
+        assert_eq!(ints(df.rank("name", RankMethod::Dense, &[])?), [Some(2), Some(1), Some(2), Some(2), Some(1), Some(2)]);
+
+        Ok(())
+    }
+}
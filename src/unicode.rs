@@ -0,0 +1,196 @@
+// Display widths of text in a terminal, without pulling in the unicode
+// tables: a condensed list of the wide and zero width ranges covers the
+// scripts and emoji that show up in data.
+
+// East Asian wide and fullwidth characters, and emoji presented as such.
+const WIDE: &[(u32, u32)] = &[
+    (0x1100, 0x115F),
+    (0x231A, 0x231B),
+    (0x2329, 0x232A),
+    (0x23E9, 0x23EC),
+    (0x23F0, 0x23F0),
+    (0x23F3, 0x23F3),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x267F, 0x267F),
+    (0x2693, 0x2693),
+    (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB),
+    (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5),
+    (0x26CE, 0x26CE),
+    (0x26D4, 0x26D4),
+    (0x26EA, 0x26EA),
+    (0x26F2, 0x26F3),
+    (0x26F5, 0x26F5),
+    (0x26FA, 0x26FA),
+    (0x26FD, 0x26FD),
+    (0x2705, 0x2705),
+    (0x270A, 0x270B),
+    (0x2728, 0x2728),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x2E80, 0x303E),
+    (0x3041, 0x33FF),
+    (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF),
+    (0xA000, 0xA4CF),
+    (0xA960, 0xA97F),
+    (0xAC00, 0xD7A3),
+    (0xF900, 0xFAFF),
+    (0xFE10, 0xFE19),
+    (0xFE30, 0xFE6F),
+    (0xFF00, 0xFF60),
+    (0xFFE0, 0xFFE6),
+    (0x16FE0, 0x16FE4),
+    (0x17000, 0x18CFF),
+    (0x1B000, 0x1B2FF),
+    (0x1F004, 0x1F004),
+    (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F1E6, 0x1F1FF),
+    (0x1F200, 0x1F251),
+    (0x1F300, 0x1F64F),
+    (0x1F680, 0x1F6FF),
+    (0x1F7E0, 0x1F7EB),
+    (0x1F90C, 0x1F9FF),
+    (0x1FA70, 0x1FAFF),
+    (0x20000, 0x2FFFD),
+    (0x30000, 0x3FFFD),
+];
+
+// Combining marks, joiners, variation selectors and emoji modifiers, which
+// draw over the character before them.
+const ZERO: &[(u32, u32)] = &[
+    (0x0300, 0x036F),
+    (0x0483, 0x0489),
+    (0x0591, 0x05BD),
+    (0x0610, 0x061A),
+    (0x064B, 0x065F),
+    (0x0670, 0x0670),
+    (0x06D6, 0x06DC),
+    (0x06DF, 0x06E4),
+    (0x0900, 0x0903),
+    (0x093A, 0x094F),
+    (0x0E31, 0x0E31),
+    (0x0E34, 0x0E3A),
+    (0x0E47, 0x0E4E),
+    (0x1160, 0x11FF),
+    (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF),
+    (0x200B, 0x200F),
+    (0x20D0, 0x20FF),
+    (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F),
+    (0x1F3FB, 0x1F3FF),
+    (0xE0020, 0xE007F),
+    (0xE0100, 0xE01EF),
+];
+
+const ZWJ: char = '\u{200D}';
+
+fn contains(ranges: &[(u32, u32)], c: char) -> bool {
+    let c = c as u32;
+    ranges
+        .binary_search_by(|&(lo, hi)| {
+            if hi < c {
+                std::cmp::Ordering::Less
+            } else if lo > c {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+fn is_regional(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+// The text split into what shows as one character: a base character with
+// the marks over it, emoji joined by ZWJ, or a pair of regional indicators.
+pub(crate) fn graphemes(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let mut chars = rest.char_indices();
+        let (_, first) = chars.next()?;
+        let mut prev = first;
+        let mut regional = is_regional(first);
+        let mut end = rest.len();
+        for (i, c) in chars {
+            let joined = prev == ZWJ || contains(ZERO, c) || (regional && is_regional(c));
+            if !joined {
+                end = i;
+                break;
+            }
+            regional = false;
+            prev = c;
+        }
+        let (grapheme, tail) = rest.split_at(end);
+        rest = tail;
+        Some(grapheme)
+    })
+}
+
+// the width of a grapheme is that of the character it starts with
+pub(crate) fn grapheme_width(grapheme: &str) -> usize {
+    match grapheme.chars().next() {
+        Some(c) if contains(WIDE, c) => 2,
+        Some(c) if contains(ZERO, c) => 0,
+        Some(_) => 1,
+        None => 0,
+    }
+}
+
+pub(crate) fn width(text: &str) -> usize {
+    graphemes(text).map(grapheme_width).sum()
+}
+
+// Control characters written as escapes, so a cell stays on one line.
+pub(crate) fn escape(text: &str) -> String {
+    if !text.chars().any(char::is_control) {
+        return text.to_string();
+    }
+    text.chars()
+        .map(|c| match c {
+            '\n' => "\\n".to_string(),
+            '\r' => "\\r".to_string(),
+            '\t' => "\\t".to_string(),
+            c if c.is_control() => c.escape_unicode().to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_width() {
+        assert_eq!(width("Nuñez"), 5);
+        assert_eq!(width("Nun\u{303}ez"), 5);
+        assert_eq!(width("孫興慜"), 6);
+        assert_eq!(width("ｓｏｎ"), 6);
+        assert_eq!(width("👨‍👩‍👧 🇺🇾"), 5);
+        assert_eq!(graphemes("Nun\u{303}ez").collect::<Vec<_>>(), ["N", "u", "n\u{303}", "e", "z"]);
+        assert_eq!(graphemes("🇺🇾🇦🇷").count(), 2);
+    }
+
+    #[test]
+    fn escaped() {
+        assert_eq!(escape("Messi"), "Messi");
+        assert_eq!(escape("line\nbreak\t\u{1b}"), "line\\nbreak\\t\\u{1b}");
+    }
+}
@@ -1,9 +1,32 @@
-use std::str::FromStr;
-use crate::Error;
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    hash::{Hash, Hasher},
+    mem::size_of,
+    str::FromStr,
+    sync::Arc,
+};
 
-#[derive(Debug, Clone)]
+use crate::{Categorical, CsvOptions, Date, Struct, Datetime, Decimal, DType, Duration, Error, Time};
+
+// Numeric variants compare and hash by their mathematical value, whatever
+// the variant: `Int8(3) == Uint64(3)` and `Float64(3.0) == Usize(3)`.
+// Decimals compare exactly with integers, floats and each other. NaN is
+// equal to itself and greater than every other number, `-0.0 == 0.0`.
+// Values of different kinds order nulls first, then booleans, numbers,
+// dates, times, datetimes, durations, strings, lists, structs and bytes,
+// categoricals ordering with strings by their text, lists, structs and
+// bytes element-wise.
+#[derive(Clone, Default)]
 pub enum Val {
+    // a missing value, like an empty csv cell
+    #[default]
+    Null,
+
+    Bool(bool),
+
     String(String),
+    Categorical(Categorical),
 
     Isize(isize),
     Usize(usize),
@@ -25,18 +48,27 @@ pub enum Val {
 
     Float64(f64),
     Float32(f32),
-}
 
-impl Default for Val {
-    fn default() -> Self {
-        Self::String(Default::default())
-    }
+    Decimal(Decimal),
+
+    Date(Date),
+    Time(Time),
+    Datetime(Datetime),
+    Duration(Duration),
+
+    List(Vec<Val>),
+    Struct(Struct),
+
+    Bytes(Vec<u8>),
 }
 
 impl std::fmt::Display for Val {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Val::Null => f.write_str("null"),
+            Val::Bool(val) => write!(f, "{}", val),
             Val::String(val) => write!(f, "{:?}", val),
+            Val::Categorical(val) => write!(f, "{:?}", val.as_str()),
             Val::Isize(val) => write!(f, "{}", val),
             Val::Usize(val) => write!(f, "{}", val),
             Val::Int128(val) => write!(f, "{}", val),
@@ -51,125 +83,373 @@ impl std::fmt::Display for Val {
             Val::Uint8(val) => write!(f, "{}", val),
             Val::Float64(val) => write!(f, "{}", val),
             Val::Float32(val) => write!(f, "{}", val),
+            Val::Decimal(val) => write!(f, "{}", val),
+            Val::Date(val) => write!(f, "{}", val),
+            Val::Time(val) => write!(f, "{}", val),
+            Val::Datetime(val) => write!(f, "{}", val),
+            Val::Duration(val) => write!(f, "{}", val),
+            Val::List(vals) => {
+                f.write_str("[")?;
+                for (i, val) in vals.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", val)?;
+                }
+                f.write_str("]")
+            },
+            Val::Struct(val) => write!(f, "{}", val),
+            Val::Bytes(val) => write!(f, "0x{}", crate::bytes::hex(val)),
+        }
+    }
+}
+
+// The derived output, except for categoricals which show their text and
+// bytes which only show a short preview.
+impl std::fmt::Debug for Val {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        macro_rules! tuple {
+            ($name:literal, $val:expr) => {
+                f.debug_tuple($name).field($val).finish()
+            };
+        }
+        match self {
+            Val::Null => f.write_str("Null"),
+            Val::Bool(val) => tuple!("Bool", val),
+            Val::String(val) => tuple!("String", val),
+            Val::Categorical(val) => tuple!("Categorical", &val.as_str()),
+            Val::Isize(val) => tuple!("Isize", val),
+            Val::Usize(val) => tuple!("Usize", val),
+            Val::Int128(val) => tuple!("Int128", val),
+            Val::UInt128(val) => tuple!("UInt128", val),
+            Val::Int64(val) => tuple!("Int64", val),
+            Val::Uint64(val) => tuple!("Uint64", val),
+            Val::Int32(val) => tuple!("Int32", val),
+            Val::Uint32(val) => tuple!("Uint32", val),
+            Val::Int16(val) => tuple!("Int16", val),
+            Val::Uint16(val) => tuple!("Uint16", val),
+            Val::Int8(val) => tuple!("Int8", val),
+            Val::Uint8(val) => tuple!("Uint8", val),
+            Val::Float64(val) => tuple!("Float64", val),
+            Val::Float32(val) => tuple!("Float32", val),
+            Val::Decimal(val) => tuple!("Decimal", val),
+            Val::Date(val) => tuple!("Date", val),
+            Val::Time(val) => tuple!("Time", val),
+            Val::Datetime(val) => tuple!("Datetime", val),
+            Val::Duration(val) => tuple!("Duration", val),
+            Val::List(val) => tuple!("List", val),
+            Val::Struct(val) => tuple!("Struct", val),
+            Val::Bytes(val) => write!(f, "Bytes({})", crate::bytes::preview(val)),
         }
     }
 }
 
 impl PartialEq for Val {
     fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (Val::String(s1), Val::String(s2)) => s1.eq(s2),
-            (Val::Int64(i1), Val::Int64(i2)) => i1.eq(i2),
-            (Val::Float64(f1), Val::Float64(f2)) => f1.eq(f2),
-            (Val::Usize(u1), Val::Usize(u2)) => u1.eq(u2),
-            _ => false
-        }
+        self.cmp(other) == Ordering::Equal
     }
 }
 
 impl Eq for Val {}
 
 impl PartialOrd for Val {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Val {
+    fn cmp(&self, other: &Self) -> Ordering {
         match (self, other) {
-            (Val::String(s1), Val::String(s2)) => Some(s1.cmp(s2)),
-            (Val::Int64(i1), Val::Int64(i2)) => Some(i1.cmp(i2)),
-            (Val::Float64(f1), Val::Float64(f2)) => Some(f1.total_cmp(f2)),
-            (Val::Usize(u1), Val::Usize(u2)) => Some(u1.cmp(u2)),
-            _ => None
+            (Val::Bool(b1), Val::Bool(b2)) => b1.cmp(b2),
+            (Val::String(s1), Val::String(s2)) => s1.cmp(s2),
+            (Val::Categorical(c1), Val::Categorical(c2)) => c1.cmp(c2),
+            (Val::String(s), Val::Categorical(c)) => s.as_str().cmp(c.as_str()),
+            (Val::Categorical(c), Val::String(s)) => c.as_str().cmp(s.as_str()),
+            (Val::Date(d1), Val::Date(d2)) => d1.cmp(d2),
+            (Val::Time(t1), Val::Time(t2)) => t1.cmp(t2),
+            (Val::Datetime(d1), Val::Datetime(d2)) => d1.cmp(d2),
+            (Val::Duration(d1), Val::Duration(d2)) => d1.cmp(d2),
+            (Val::Decimal(d1), Val::Decimal(d2)) => d1.cmp(d2),
+            (Val::List(l1), Val::List(l2)) => l1.cmp(l2),
+            (Val::Struct(s1), Val::Struct(s2)) => s1.cmp(s2),
+            (Val::Bytes(b1), Val::Bytes(b2)) => b1.cmp(b2),
+            (Val::Decimal(d), _) if other.num().is_some() => decimal_cmp(d, other),
+            (_, Val::Decimal(d)) if self.num().is_some() => decimal_cmp(d, self).reverse(),
+            _ => match (self.num(), other.num()) {
+                (Some(n1), Some(n2)) => n1.cmp(n2),
+                _ => self.rank().cmp(&other.rank()),
+            }
         }
     }
 }
 
-// FIXME: use derive macro to turn a struct into dataframe
-impl FromStr for Val {
-    type Err = Error;
-    fn from_str(value: &str) -> Result<Self, Self::Err> {
-        let val = value.parse::<i64>()
-            .map_or_else(|_| {
-                value
-                    .parse::<f64>()
-                    .map_or_else(|_| Val::String(value.to_string()),
-                    Val::Float64
-                )
+impl Hash for Val {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rank().hash(state);
+        match self {
+            Val::Bool(b) => b.hash(state),
+            Val::String(s) => s.hash(state),
+            Val::Categorical(c) => c.hash(state),
+            Val::Date(d) => d.hash(state),
+            Val::Time(t) => t.hash(state),
+            Val::Datetime(d) => d.hash(state),
+            Val::Duration(d) => d.hash(state),
+            Val::List(l) => l.hash(state),
+            Val::Struct(s) => s.hash(state),
+            Val::Bytes(b) => b.hash(state),
+            // like the integer or float it equals, if any
+            Val::Decimal(d) => match (d.to_i128(), d.to_f64()) {
+                (Some(i), _) => Num::Int(i).hash(state),
+                (None, f) if d.cmp_f64(f).is_eq() => Num::Float(f).hash(state),
+                (None, _) => d.hash(state),
             },
-            Val::Int64
-        );
-        Ok(val)
+            _ => if let Some(num) = self.num() {
+                num.hash(state)
+            }
+        }
     }
 }
 
-impl From<String> for Val {
-    fn from(value: String) -> Self {
-        Self::String(value)
-    }
+// Every numeric variant seen as the widest type of its kind, so that values
+// can be compared across variants by their mathematical value.
+#[derive(Debug, Clone, Copy)]
+enum Num {
+    Int(i128),
+    UInt(u128),
+    Float(f64),
 }
 
-impl From<&String> for Val {
-    fn from(value: &String) -> Self {
-        Self::String(value.clone())
+// 2^127 and 2^128, the bounds of i128 and u128 as exact floats
+const I128_BOUND: f64 = 170141183460469231731687303715884105728.0;
+const U128_BOUND: f64 = 340282366920938463463374607431768211456.0;
+
+impl Num {
+    fn cmp(self, other: Self) -> Ordering {
+        match (self, other) {
+            (Num::Int(i1), Num::Int(i2)) => i1.cmp(&i2),
+            (Num::UInt(u1), Num::UInt(u2)) => u1.cmp(&u2),
+            (Num::Int(i), Num::UInt(u)) => {
+                if i < 0 { Ordering::Less } else { (i as u128).cmp(&u) }
+            },
+            (Num::Float(f1), Num::Float(f2)) => match (f1.is_nan(), f2.is_nan()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => f1.partial_cmp(&f2).unwrap_or(Ordering::Equal),
+            },
+            (Num::Float(f), Num::Int(i)) => {
+                if f.is_nan() || f >= I128_BOUND {
+                    Ordering::Greater
+                } else if f < -I128_BOUND {
+                    Ordering::Less
+                } else {
+                    (f.trunc() as i128).cmp(&i).then(f.fract().partial_cmp(&0.0).unwrap_or(Ordering::Equal))
+                }
+            },
+            (Num::Float(f), Num::UInt(u)) => {
+                if f.is_nan() || f >= U128_BOUND {
+                    Ordering::Greater
+                } else if f < 0.0 {
+                    Ordering::Less
+                } else {
+                    (f.trunc() as u128).cmp(&u).then(f.fract().partial_cmp(&0.0).unwrap_or(Ordering::Equal))
+                }
+            },
+            (Num::UInt(_), Num::Int(_))
+            | (Num::Int(_), Num::Float(_))
+            | (Num::UInt(_), Num::Float(_)) => other.cmp(self).reverse(),
+        }
     }
-}
 
-impl From<i64> for Val {
-    fn from(value: i64) -> Self {
-        Self::Int64(value)
+    // Equal numbers hash the same whatever their kind: integral values hash
+    // as an integer, anything else by its bits.
+    fn hash<H: Hasher>(self, state: &mut H) {
+        match self {
+            Num::Int(i) => (0u8, i).hash(state),
+            Num::UInt(u) => match i128::try_from(u) {
+                Ok(i) => (0u8, i).hash(state),
+                Err(_) => (1u8, u).hash(state),
+            },
+            Num::Float(f) if f.is_nan() => 2u8.hash(state),
+            Num::Float(f) if f.fract() == 0.0 && (-I128_BOUND..I128_BOUND).contains(&f) => Num::Int(f as i128).hash(state),
+            Num::Float(f) if f.fract() == 0.0 && (0.0..U128_BOUND).contains(&f) => Num::UInt(f as u128).hash(state),
+            Num::Float(f) => (3u8, f.to_bits()).hash(state),
+        }
     }
 }
 
-impl From<usize> for Val {
-    fn from(value: usize) -> Self {
-        Self::Usize(value)
+// a decimal against an integer, which may not fit in a decimal, or a float
+fn decimal_cmp(d: &Decimal, num: &Val) -> Ordering {
+    match num.num() {
+        Some(Num::Int(i)) => d.cmp_int(i),
+        Some(Num::UInt(u)) => i128::try_from(u).map_or(Ordering::Less, |i| d.cmp_int(i)),
+        Some(Num::Float(f)) => d.cmp_f64(f),
+        None => Ordering::Equal,
     }
 }
 
-impl From<f64> for Val {
-    fn from(value: f64) -> Self {
-        Self::Float64(value)
+impl FromStr for Val {
+    type Err = Error;
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(value, &CsvOptions::default())
     }
 }
 
-impl TryFrom<&Val> for String {
-    type Error = Error;
-    fn try_from(value: &Val) -> Result<Self, Self::Error> {
-        match value {
-            Val::String(s) => Ok(s.to_owned()),
-            _ => Err(Error::ValToString)
+impl Val {
+    // Infers the variant of a csv cell: empty cells are null rather than an
+    // empty string, then booleans, integers, floats, datetimes, dates and
+    // times are tried before falling back to a string.
+    pub fn parse_with(value: &str, options: &CsvOptions) -> Result<Self, Error> {
+        if value.is_empty() {
+            return Ok(Val::Null);
+        }
+        if let Some(b) = options.parse_bool(value) {
+            return Ok(Val::Bool(b));
+        }
+        if let Ok(n) = value.parse::<i64>() {
+            return Ok(Val::Int64(n));
         }
+        if let Ok(f) = value.parse::<f64>() {
+            return Ok(Val::Float64(f));
+        }
+        Ok(options.parse_temporal(value).unwrap_or_else(|| Val::String(value.to_string())))
     }
-}
 
-impl TryFrom<&Val> for usize {
-    type Error = Error;
-    fn try_from(value: &Val) -> Result<Self, Self::Error> {
-        match value {
-            Val::Usize(n) => Ok(*n),
-            _ => Err(Error::ValToUsize)
+    // Reads a csv cell as the given type, empty cells being null.
+    pub fn parse_as(value: &str, dtype: DType, options: &CsvOptions) -> Result<Self, Error> {
+        if value.is_empty() {
+            return Ok(Val::Null);
+        }
+        let invalid = || Error::ValParseError(value.to_string());
+        macro_rules! parse {
+            ($variant:ident) => {
+                value.parse().map(Val::$variant).map_err(|_| invalid())
+            };
+        }
+        match dtype {
+            DType::Bool => options.parse_bool(value).map(Val::Bool).ok_or_else(invalid),
+            // categoricals are encoded once the whole column is read
+            DType::String | DType::Categorical => Ok(Val::String(value.to_string())),
+            DType::Isize => parse!(Isize),
+            DType::Usize => parse!(Usize),
+            DType::Int128 => parse!(Int128),
+            DType::UInt128 => parse!(UInt128),
+            DType::Int64 => parse!(Int64),
+            DType::Uint64 => parse!(Uint64),
+            DType::Int32 => parse!(Int32),
+            DType::Uint32 => parse!(Uint32),
+            DType::Int16 => parse!(Int16),
+            DType::Uint16 => parse!(Uint16),
+            DType::Int8 => parse!(Int8),
+            DType::Uint8 => parse!(Uint8),
+            DType::Float64 => parse!(Float64),
+            DType::Float32 => parse!(Float32),
+            DType::Decimal { precision, scale } => Decimal::parse_with(value, precision, scale).map(Val::Decimal),
+            DType::Date => options.parse_date(value).map(Val::Date).ok_or_else(invalid),
+            DType::Time => options.parse_time(value).map(Val::Time).ok_or_else(invalid),
+            DType::Datetime => options.parse_datetime(value).map(Val::Datetime).ok_or_else(invalid),
+            DType::Bytes => options.bytes_encoding.decode(value).map(Val::Bytes).ok_or_else(invalid),
+            DType::Null | DType::Duration | DType::List | DType::Struct => Err(Error::InvalidDataType(dtype.to_string())),
+        }
+    }
+
+    // Bytes owned on the heap. Shared categorical dictionaries only count the
+    // first time `seen` meets them.
+    pub(crate) fn heap_size(&self, seen: &mut HashSet<*const [String]>) -> usize {
+        match self {
+            Val::String(s) => s.capacity(),
+            Val::Bytes(b) => b.capacity(),
+            Val::List(l) => l.capacity() * size_of::<Val>() + l.iter().map(|val| val.heap_size(seen)).sum::<usize>(),
+            Val::Struct(s) => s.fields().iter().map(|(name, val)| size_of::<(String, Val)>() + name.capacity() + val.heap_size(seen)).sum(),
+            Val::Categorical(c) if seen.insert(Arc::as_ptr(c.categories())) => {
+                // the strong and weak counts, then the strings
+                2 * size_of::<usize>() + c.categories().iter().map(|s| size_of::<String>() + s.capacity()).sum::<usize>()
+            },
+            _ => 0,
         }
     }
-}
 
-impl TryFrom<&Val> for i64 {
-    type Error = Error;
-    fn try_from(value: &Val) -> Result<Self, Self::Error> {
-        match value {
-            Val::Int64(n) => Ok(*n),
-            _ => Err(Error::ValToInt64)
+    fn num(&self) -> Option<Num> {
+        let num = match self {
+            Val::Isize(n) => Num::Int(*n as i128),
+            Val::Usize(n) => Num::UInt(*n as u128),
+            Val::Int128(n) => Num::Int(*n),
+            Val::UInt128(n) => Num::UInt(*n),
+            Val::Int64(n) => Num::Int(*n as i128),
+            Val::Uint64(n) => Num::UInt(*n as u128),
+            Val::Int32(n) => Num::Int(*n as i128),
+            Val::Uint32(n) => Num::UInt(*n as u128),
+            Val::Int16(n) => Num::Int(*n as i128),
+            Val::Uint16(n) => Num::UInt(*n as u128),
+            Val::Int8(n) => Num::Int(*n as i128),
+            Val::Uint8(n) => Num::UInt(*n as u128),
+            Val::Float64(n) => Num::Float(*n),
+            Val::Float32(n) => Num::Float(*n as f64),
+            Val::Decimal(d) => d.to_i128().map_or_else(|| Num::Float(d.to_f64()), Num::Int),
+            Val::Null
+            | Val::Bool(_)
+            | Val::String(_)
+            | Val::Categorical(_)
+            | Val::Date(_)
+            | Val::Time(_)
+            | Val::Datetime(_)
+            | Val::Duration(_)
+            | Val::List(_)
+            | Val::Struct(_)
+            | Val::Bytes(_) => return None,
+        };
+        Some(num)
+    }
+
+    // the order between values of different kinds
+    fn rank(&self) -> u8 {
+        match self {
+            Val::Null => 0,
+            Val::Bool(_) => 1,
+            Val::Date(_) => 3,
+            Val::Time(_) => 4,
+            Val::Datetime(_) => 5,
+            Val::Duration(_) => 6,
+            Val::String(_) | Val::Categorical(_) => 7,
+            Val::List(_) => 8,
+            Val::Struct(_) => 9,
+            Val::Bytes(_) => 10,
+            _ => 2,
         }
     }
-}
 
-impl TryFrom<&Val> for f64 {
-    type Error = Error;
-    fn try_from(value: &Val) -> Result<Self, Self::Error> {
-        match value {
-            Val::Float64(n) => Ok(*n),
-            _ => Err(Error::ValToFloat64)
+    pub fn dtype(&self) -> DType {
+        match self {
+            Val::Null => DType::Null,
+            Val::Bool(_) => DType::Bool,
+            Val::String(_) => DType::String,
+            Val::Categorical(_) => DType::Categorical,
+            Val::Isize(_) => DType::Isize,
+            Val::Usize(_) => DType::Usize,
+            Val::Int128(_) => DType::Int128,
+            Val::UInt128(_) => DType::UInt128,
+            Val::Int64(_) => DType::Int64,
+            Val::Uint64(_) => DType::Uint64,
+            Val::Int32(_) => DType::Int32,
+            Val::Uint32(_) => DType::Uint32,
+            Val::Int16(_) => DType::Int16,
+            Val::Uint16(_) => DType::Uint16,
+            Val::Int8(_) => DType::Int8,
+            Val::Uint8(_) => DType::Uint8,
+            Val::Float64(_) => DType::Float64,
+            Val::Float32(_) => DType::Float32,
+            Val::Decimal(d) => d.dtype(),
+            Val::Date(_) => DType::Date,
+            Val::Time(_) => DType::Time,
+            Val::Datetime(_) => DType::Datetime,
+            Val::Duration(_) => DType::Duration,
+            Val::List(_) => DType::List,
+            Val::Struct(_) => DType::Struct,
+            Val::Bytes(_) => DType::Bytes,
         }
     }
-}
 
-impl Val {
     pub fn is_float(&self) -> bool {
         matches!(self, Self::Float64(_))
     }
@@ -185,4 +465,179 @@ impl Val {
     pub fn is_str(&self) -> bool {
         matches!(self, Val::String(_))
     }
+
+    pub fn is_bool(&self) -> bool {
+        matches!(self, Val::Bool(_))
+    }
+
+    pub fn is_temporal(&self) -> bool {
+        self.dtype().is_temporal()
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Val::Null)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn eq_every_variant() {
+        let vals = [
+            Val::String("3".to_string()),
+            Val::Isize(3),
+            Val::Usize(3),
+            Val::Int128(3),
+            Val::UInt128(3),
+            Val::Int64(3),
+            Val::Uint64(3),
+            Val::Int32(3),
+            Val::Uint32(3),
+            Val::Int16(3),
+            Val::Uint16(3),
+            Val::Int8(3),
+            Val::Uint8(3),
+            Val::Float64(3.0),
+            Val::Float32(3.0),
+        ];
+
+        vals.iter().for_each(|val| assert_eq!(val, &val.clone()));
+        vals[1..].iter().for_each(|val| {
+            assert_eq!(val, &Val::Int8(3));
+            assert_ne!(val, &vals[0]);
+        });
+    }
+
+    #[test]
+    fn cmp_across_variants() {
+        assert!(Val::Int8(-1) < Val::Uint8(0));
+        assert!(Val::UInt128(u128::MAX) > Val::Int128(i128::MAX));
+        assert!(Val::Float64(2.5) > Val::Int64(2));
+        assert!(Val::Float64(-2.5) < Val::Int64(-2));
+        assert!(Val::Float32(1e30) < Val::UInt128(u128::MAX));
+        assert!(Val::Float64(f64::INFINITY) > Val::UInt128(u128::MAX));
+        assert!(Val::Float64(f64::NEG_INFINITY) < Val::Int128(i128::MIN));
+        assert!(Val::Int64(i64::MAX) < Val::String(String::new()));
+        assert_ne!(Val::Float64(0.1), Val::Int8(0));
+    }
+
+    #[test]
+    fn null() -> Result<(), Error> {
+        assert_eq!(Val::Null, Val::Null);
+        assert!(Val::Null < Val::Int64(i64::MIN));
+        assert!("".parse::<Val>()?.is_null());
+        assert!(Val::default().is_null());
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_bool() -> Result<(), Error> {
+        assert_eq!("TRUE".parse::<Val>()?, Val::Bool(true));
+        assert_eq!("false".parse::<Val>()?, Val::Bool(false));
+        assert_eq!("no".parse::<Val>()?, Val::String("no".into()));
+        assert_eq!("1".parse::<Val>()?, Val::Int64(1));
+
+        let options = CsvOptions {
+            true_values: vec!["1".to_string(), "Y".to_string()],
+            false_values: vec!["0".to_string(), "N".to_string()],
+            ..CsvOptions::default()
+        };
+        assert_eq!(Val::parse_with("1", &options)?, Val::Bool(true));
+        assert_eq!(Val::parse_with("n", &options)?, Val::Bool(false));
+        assert_eq!(Val::parse_with("yes", &options)?, Val::String("yes".into()));
+        assert!(Val::Bool(true) > Val::Bool(false));
+        assert!(Val::Bool(true) < Val::Int8(0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn nan_and_zero() {
+        assert_eq!(Val::Float64(f64::NAN), Val::Float32(f32::NAN));
+        assert!(Val::Float64(f64::NAN) > Val::Float64(f64::INFINITY));
+        assert!(Val::Float64(f64::NAN) > Val::UInt128(u128::MAX));
+        assert_eq!(Val::Float64(-0.0), Val::Int8(0));
+    }
+
+    #[test]
+    fn hash_agrees_with_eq() {
+        let set = [
+            Val::Int8(3),
+            Val::Uint64(3),
+            Val::Float32(3.0),
+            Val::Float64(-0.0),
+            Val::Usize(0),
+            Val::Float64(f64::NAN),
+            Val::Float32(f32::NAN),
+            Val::UInt128(1 << 127),
+            Val::Float64(I128_BOUND),
+            Val::Float64(0.5),
+            Val::String("3".to_string()),
+        ]
+        .into_iter()
+        .collect::<HashSet<_>>();
+
+        assert_eq!(set.len(), 6);
+    }
+
+    #[test]
+    fn decimal_by_value() -> Result<(), Error> {
+        let dec = |input: &str| input.parse().map(Val::Decimal);
+
+        assert_eq!(dec("3.00")?, Val::Int8(3));
+        assert_eq!(dec("0.50")?, Val::Float64(0.5));
+        assert!(dec("2.99")? < Val::Uint8(3));
+        assert!(Val::Int128(i128::MAX) > dec("99.5")?);
+
+        let set = [dec("3.00")?, Val::Int64(3), dec("1.5")?, dec("1.50")?, Val::Float32(1.5)]
+            .into_iter()
+            .collect::<HashSet<_>>();
+        assert_eq!(set.len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn decimal_against_floats() -> Result<(), Error> {
+        let dec = |input: &str| input.parse().map(Val::Decimal);
+        let (close, tenth) = (dec("0.10000000000000000001")?, dec("0.1")?);
+
+        // 0.1 isn't exact as a float, so neither decimal equals it
+        assert_ne!(close, tenth);
+        assert_ne!(close, Val::Float64(0.1));
+        assert_ne!(tenth, Val::Float64(0.1));
+        assert!(tenth < close && close < Val::Float64(0.1));
+        assert!(dec("-0.1")? > Val::Float64(-0.1));
+        assert_eq!(dec("-2.375")?, Val::Float32(-2.375));
+        assert_eq!(dec("0.000000000931322574615478515625")?, Val::Float64(2f64.powi(-30)));
+        assert!(dec("0.0000000000000001110223024625156540")? < Val::Float64(2f64.powi(-53)));
+        assert!(dec("5")? < Val::Float64(f64::INFINITY) && dec("5")? > Val::Float64(f64::NEG_INFINITY));
+        assert!(dec("5")? < Val::Float64(f64::NAN) && dec("0")? > Val::Float64(-f64::MIN_POSITIVE / 4.0));
+
+        let set = [close, tenth, Val::Float64(0.1), dec("0.375")?, Val::Float64(0.375), dec("-2.375")?, Val::Float32(-2.375)]
+            .into_iter()
+            .collect::<HashSet<_>>();
+        assert_eq!(set.len(), 5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn bytes_by_value() {
+        let bytes = |b: &[u8]| Val::Bytes(b.to_vec());
+
+        assert_eq!(bytes(b"ab"), bytes(b"ab"));
+        assert!(bytes(b"ab") < bytes(b"b"));
+        assert!(bytes(b"") > Val::from(vec![1u8]));
+        assert_eq!([bytes(b"ab"), bytes(b"ab"), bytes(b"ba")].into_iter().collect::<HashSet<_>>().len(), 2);
+        assert_eq!(bytes(&[0xca, 0xfe]).to_string(), "0xcafe");
+        assert_eq!(format!("{:?}", bytes(&[0; 40])), format!("Bytes(0x{}…, 40 bytes)", "00".repeat(16)));
+        assert_eq!(format!("{:?}", Val::Int8(1)), "Int8(1)");
+        assert_eq!(Vec::<u8>::try_from(&bytes(b"ab")).ok(), Some(b"ab".to_vec()));
+    }
 }
@@ -0,0 +1,170 @@
+use crate::{reduce, Column, DataFrame, Error, Val};
+
+// A rolling or expanding window over a column, built by
+// `DataFrame::rolling` and `DataFrame::expanding`. Each aggregation gives a
+// column aligned with the rows of the frame, null where the window holds
+// fewer than `min_periods` non-null values. Every window is aggregated from
+// scratch, so a rolling window of `w` rows costs O(n * w) over `n` rows and
+// an expanding one O(n²).
+#[derive(Debug, Clone)]
+pub struct Window<'a> {
+    df: &'a DataFrame,
+    column: String,
+    // the rows in a window, every row so far when `None`
+    size: Option<usize>,
+    min_periods: usize,
+    center: bool,
+    partition_by: Vec<String>,
+}
+
+impl DataFrame {
+    // the last `window` rows up to each row
+    pub fn rolling(&self, column: &str, window: usize, min_periods: usize) -> Window<'_> {
+        Window {
+            df: self,
+            column: column.to_string(),
+            size: Some(window),
+            min_periods,
+            center: false,
+            partition_by: Vec::new(),
+        }
+    }
+
+    // every row up to each row
+    pub fn expanding(&self, column: &str, min_periods: usize) -> Window<'_> {
+        Window {
+            df: self,
+            column: column.to_string(),
+            size: None,
+            min_periods,
+            center: false,
+            partition_by: Vec::new(),
+        }
+    }
+}
+
+impl Window<'_> {
+    // Centres rolling windows on their row rather than ending them there,
+    // an even window reaching one row further back than forward.
+    pub fn center(mut self, center: bool) -> Self {
+        self.center = center;
+        self
+    }
+
+    // restarts the windows for each group of equal values of `columns`
+    pub fn partition_by(mut self, columns: &[&str]) -> Self {
+        self.partition_by = columns.iter().map(|column| column.to_string()).collect();
+        self
+    }
+
+    // the range of positions in the window of the row at `pos` out of `len`
+    fn bounds(&self, pos: usize, len: usize) -> (usize, usize) {
+        match self.size {
+            None => (0, pos + 1),
+            Some(size) if self.center => {
+                let start = pos.saturating_sub(size / 2);
+                (start, pos.saturating_add(size - size / 2).min(len))
+            },
+            Some(size) => (pos + 1 - size.min(pos + 1), pos + 1),
+        }
+    }
+
+    fn apply(&self, f: fn(&[&Val]) -> Result<Val, Error>) -> Result<Column, Error> {
+        if self.size == Some(0) {
+            return Err(Error::EmptyWindow);
+        }
+        let df = self.df;
+        let col = df.position(&self.column)?;
+
+        let by = self.partition_by.iter().map(String::as_str).collect::<Vec<_>>();
+        let mut values = vec![Val::Null; df.height];
+        for rows in df.partitions(&by)? {
+            let vals = rows.iter().map(|row| &df.data[row * df.width + col]).collect::<Vec<_>>();
+            for (pos, &row) in rows.iter().enumerate() {
+                let (start, end) = self.bounds(pos, rows.len());
+                let window = &vals[start..end];
+                if window.iter().filter(|val| !val.is_null()).count() >= self.min_periods {
+                    values[row] = f(window)?;
+                }
+            }
+        }
+        Ok(Column::new(self.column.clone(), values))
+    }
+
+    pub fn sum(&self) -> Result<Column, Error> {
+        self.apply(reduce::sum)
+    }
+
+    pub fn mean(&self) -> Result<Column, Error> {
+        self.apply(reduce::mean)
+    }
+
+    pub fn min(&self) -> Result<Column, Error> {
+        self.apply(reduce::min)
+    }
+
+    pub fn max(&self) -> Result<Column, Error> {
+        self.apply(reduce::max)
+    }
+
+    pub fn std(&self) -> Result<Column, Error> {
+        self.apply(reduce::std)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn df() -> DataFrame {
+        let csv = "name,goals
+Lionel Messi,1
+Darwin Nunez,4
+Lionel Messi,2
+Lionel Messi,
+Darwin Nunez,0
+Lionel Messi,3
+";
+        DataFrame::read_str(csv.to_string()).unwrap()
+    }
+
+    fn floats(column: Column) -> Vec<Option<f64>> {
+        column.iter().map(|val| val.as_f64()).collect()
+    }
+
+    #[test]
+    fn rolling() -> Result<(), Error> {
+        let df = df();
+
+        assert_eq!(floats(df.rolling("goals", 2, 2).sum()?), [None, Some(5.0), Some(6.0), None, None, Some(3.0)]);
+        assert_eq!(floats(df.rolling("goals", 2, 1).sum()?), [Some(1.0), Some(5.0), Some(6.0), Some(2.0), Some(0.0), Some(3.0)]);
+        assert_eq!(floats(df.rolling("goals", 3, 1).center(true).max()?), [Some(4.0), Some(4.0), Some(4.0), Some(2.0), Some(3.0), Some(3.0)]);
+        assert_eq!(floats(df.rolling("goals", 4, 1).center(true).min()?), [Some(1.0), Some(1.0), Some(1.0), Some(0.0), Some(0.0), Some(0.0)]);
+        assert_eq!(df.rolling("goals", 3, 2).std()?.values()[2], Val::Float64(1.5275252316519468));
+        assert_eq!(df.rolling("goals", 2, 1).mean()?.name(), "goals");
+
+        assert!(matches!(df.rolling("goals", 0, 1).sum(), Err(Error::EmptyWindow)));
+        assert!(matches!(df.rolling("xg", 0, 1).sum(), Err(Error::EmptyWindow)));
+        assert_eq!(floats(df.rolling("goals", usize::MAX, 1).center(true).sum()?), [Some(10.0); 6]);
+        assert_eq!(floats(df.rolling("goals", usize::MAX, 1).max()?), [Some(1.0), Some(4.0), Some(4.0), Some(4.0), Some(4.0), Some(4.0)]);
+        assert!(matches!(df.rolling("xg", 2, 1).sum(), Err(Error::HeaderNotFound(_))));
+        assert!(matches!(df.rolling("name", 2, 1).mean(), Err(Error::UnsupportedOperation(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn partitioned_and_expanding() -> Result<(), Error> {
+        let df = df();
+        let form = df.rolling("goals", 2, 1).partition_by(&["name"]).mean()?;
+
+        assert_eq!(floats(form), [Some(1.0), Some(4.0), Some(1.5), Some(2.0), Some(2.0), Some(3.0)]);
+        assert_eq!(floats(df.expanding("goals", 1).max()?), [Some(1.0), Some(4.0), Some(4.0), Some(4.0), Some(4.0), Some(4.0)]);
+        assert_eq!(
+            floats(df.expanding("goals", 2).partition_by(&["name"]).sum()?),
+            [None, None, Some(3.0), Some(3.0), Some(4.0), Some(6.0)]
+        );
+
+        Ok(())
+    }
+}
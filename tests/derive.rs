@@ -0,0 +1,55 @@
+use dataframe::{
+    macros::{DataFrame, DataFrameEnum},
+    DType, Error, Val,
+};
+
+#[test]
+fn derive() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/pass/*.rs");
+    t.compile_fail("tests/ui/fail/*.rs");
+}
+
+#[derive(DataFrameEnum, Debug, Clone, Copy, PartialEq)]
+enum Foot {
+    Left,
+    Right,
+    #[dataframe(rename = "both \"feet\"")]
+    Both,
+    #[dataframe(rename = r##"n/a "#"##)]
+    Unknown,
+}
+
+#[derive(DataFrame, Debug, PartialEq)]
+struct Player {
+    name: String,
+    foot: Foot,
+}
+
+#[test]
+fn enum_columns() -> Result<(), Error> {
+    assert_eq!(Foot::VARIANTS, &["Left", "Right", "both \"feet\"", r##"n/a "#"##]);
+
+    let csv = "name,foot\nMessi,Left\nRonaldo,Right\nNeymar,n/a \"#\nDybala,Left\nPele,\"both \"\"feet\"\"\"\n";
+    let df = Player::read_str(csv.to_string())?;
+    let foot = df.col("foot").unwrap();
+    assert!(foot.iter().all(|val| val.dtype() == DType::Categorical));
+    assert_eq!(foot[2], &Val::from("n/a \"#"));
+    match (foot[0], foot[1], foot[3]) {
+        (Val::Categorical(left), Val::Categorical(right), Val::Categorical(again)) => {
+            assert!(left.shares_categories(right) && left.shares_categories(again));
+            assert_eq!(left.categories().len(), 4);
+            assert_eq!((left.code(), right.code(), again.code()), (0, 1, 0));
+        }
+        _ => panic!("foot should be categorical"),
+    }
+
+    let records = Player::read_records(csv)?;
+    assert_eq!(records[2].foot, Foot::Unknown);
+    assert_eq!(records[4].foot, Foot::Both);
+    let written = Player::to_dataframe(&records);
+    assert_eq!(written.col("foot"), df.col("foot"));
+    assert!(matches!(Player::read_records("name,foot\nMessi,left"), Err(Error::UnknownVariant { .. })));
+
+    Ok(())
+}
@@ -0,0 +1,12 @@
+#[derive(dataframe::macros::DataFrameEnum)]
+enum Position {
+    Goalkeeper,
+    Outfield(u8),
+}
+
+#[derive(dataframe::macros::DataFrame)]
+enum Player {
+    Messi,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#[derive(dataframe::macros::DataFrame)]
+struct Stats {
+    xg: f64,
+}
+
+#[derive(dataframe::macros::DataFrame)]
+struct Player {
+    #[dataframe(prefix = "stats_")]
+    stats: Stats,
+}
+
+fn main() {}
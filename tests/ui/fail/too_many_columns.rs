@@ -0,0 +1,113 @@
+use dataframe::macros::DataFrame;
+
+// 32 columns
+#[derive(DataFrame)]
+struct Wide {
+    c0: u8,
+    c1: u8,
+    c2: u8,
+    c3: u8,
+    c4: u8,
+    c5: u8,
+    c6: u8,
+    c7: u8,
+    c8: u8,
+    c9: u8,
+    c10: u8,
+    c11: u8,
+    c12: u8,
+    c13: u8,
+    c14: u8,
+    c15: u8,
+    c16: u8,
+    c17: u8,
+    c18: u8,
+    c19: u8,
+    c20: u8,
+    c21: u8,
+    c22: u8,
+    c23: u8,
+    c24: u8,
+    c25: u8,
+    c26: u8,
+    c27: u8,
+    c28: u8,
+    c29: u8,
+    c30: u8,
+    c31: u8,
+}
+
+// 33 * 32 = 1056 columns, over the cap of 1024
+#[derive(DataFrame)]
+struct Wider {
+    #[dataframe(flatten, prefix = "w0_")]
+    w0: Wide,
+    #[dataframe(flatten, prefix = "w1_")]
+    w1: Wide,
+    #[dataframe(flatten, prefix = "w2_")]
+    w2: Wide,
+    #[dataframe(flatten, prefix = "w3_")]
+    w3: Wide,
+    #[dataframe(flatten, prefix = "w4_")]
+    w4: Wide,
+    #[dataframe(flatten, prefix = "w5_")]
+    w5: Wide,
+    #[dataframe(flatten, prefix = "w6_")]
+    w6: Wide,
+    #[dataframe(flatten, prefix = "w7_")]
+    w7: Wide,
+    #[dataframe(flatten, prefix = "w8_")]
+    w8: Wide,
+    #[dataframe(flatten, prefix = "w9_")]
+    w9: Wide,
+    #[dataframe(flatten, prefix = "w10_")]
+    w10: Wide,
+    #[dataframe(flatten, prefix = "w11_")]
+    w11: Wide,
+    #[dataframe(flatten, prefix = "w12_")]
+    w12: Wide,
+    #[dataframe(flatten, prefix = "w13_")]
+    w13: Wide,
+    #[dataframe(flatten, prefix = "w14_")]
+    w14: Wide,
+    #[dataframe(flatten, prefix = "w15_")]
+    w15: Wide,
+    #[dataframe(flatten, prefix = "w16_")]
+    w16: Wide,
+    #[dataframe(flatten, prefix = "w17_")]
+    w17: Wide,
+    #[dataframe(flatten, prefix = "w18_")]
+    w18: Wide,
+    #[dataframe(flatten, prefix = "w19_")]
+    w19: Wide,
+    #[dataframe(flatten, prefix = "w20_")]
+    w20: Wide,
+    #[dataframe(flatten, prefix = "w21_")]
+    w21: Wide,
+    #[dataframe(flatten, prefix = "w22_")]
+    w22: Wide,
+    #[dataframe(flatten, prefix = "w23_")]
+    w23: Wide,
+    #[dataframe(flatten, prefix = "w24_")]
+    w24: Wide,
+    #[dataframe(flatten, prefix = "w25_")]
+    w25: Wide,
+    #[dataframe(flatten, prefix = "w26_")]
+    w26: Wide,
+    #[dataframe(flatten, prefix = "w27_")]
+    w27: Wide,
+    #[dataframe(flatten, prefix = "w28_")]
+    w28: Wide,
+    #[dataframe(flatten, prefix = "w29_")]
+    w29: Wide,
+    #[dataframe(flatten, prefix = "w30_")]
+    w30: Wide,
+    #[dataframe(flatten, prefix = "w31_")]
+    w31: Wide,
+    #[dataframe(flatten, prefix = "w32_")]
+    w32: Wide,
+}
+
+fn main() {
+    let _ = Wider::SCHEMA;
+}
@@ -0,0 +1,4 @@
+#[derive(dataframe::macros::DataFrame)]
+struct Player(String, usize);
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(dataframe::macros::DataFrame)]
+#[dataframe(lenient)]
+struct Player {
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(dataframe::macros::DataFrame)]
+struct Player {
+    name: String,
+    tags: Vec<String>,
+}
+
+fn main() {}
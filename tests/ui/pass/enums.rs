@@ -0,0 +1,40 @@
+use dataframe::{
+    macros::{DataFrame, DataFrameEnum},
+    DType, Error, Val,
+};
+
+#[derive(DataFrameEnum, Debug, PartialEq)]
+pub enum Position {
+    #[dataframe(rename = "GK")]
+    Goalkeeper,
+    #[dataframe(rename = r"DF")]
+    Defender,
+    /// Midfielder
+    MF,
+    FW = 10,
+}
+
+#[derive(DataFrame, Debug, PartialEq)]
+struct Player<'a> {
+    name: &'a str,
+    position: Position,
+}
+
+fn main() {
+    assert_eq!(Position::VARIANTS, &["GK", "DF", "MF", "FW"]);
+    assert_eq!(Player::SCHEMA, &[("name", DType::String), ("position", DType::Categorical)]);
+
+    let records = Player::read_records("name,position\nAlisson,GK\nMessi,FW").unwrap();
+    assert_eq!(records[0].position, Position::Goalkeeper);
+    assert_eq!(records[1].position, Position::FW);
+
+    let df = Player::to_dataframe(&records);
+    assert_eq!(df.col("position").unwrap(), vec![&Val::String("GK".into()), &Val::String("FW".into())]);
+
+    match Player::read_records("name,position\nMessi,Striker") {
+        Err(err @ Error::UnknownVariant { .. }) => {
+            assert_eq!(err.to_string(), "Unknown variant \"Striker\", expected one of GK, DF, MF, FW");
+        }
+        _ => panic!("Striker isn't a position"),
+    }
+}
@@ -0,0 +1,61 @@
+use dataframe::{macros::DataFrame, DType, Val};
+
+#[derive(DataFrame, Debug, PartialEq)]
+struct PlayerStats {
+    xg: f64,
+    goals: u32,
+}
+
+#[derive(DataFrame, Debug, PartialEq)]
+struct Club<'a> {
+    name: &'a str,
+    #[dataframe(flatten, prefix = "club_")]
+    stats: PlayerStats,
+}
+
+#[derive(DataFrame, Debug, PartialEq)]
+#[dataframe(strict)]
+struct Player<'a> {
+    name: &'a str,
+    #[dataframe(flatten)]
+    stats: PlayerStats,
+    #[dataframe(flatten, prefix = "club.")]
+    club: Club<'a>,
+}
+
+const CSV: &str = "club.name,stats.goals,name,stats.xg,club.club_xg,club.club_goals
+Inter Miami,66,Lionel Messi,66.66,30.5,40
+Liverpool,6969,Darwin Nunez,69.69,80.1,86";
+
+fn main() {
+    assert_eq!(
+        Player::SCHEMA,
+        &[
+            ("name", DType::String),
+            ("stats.xg", DType::Float64),
+            ("stats.goals", DType::Uint32),
+            ("club.name", DType::String),
+            ("club.club_xg", DType::Float64),
+            ("club.club_goals", DType::Uint32),
+        ]
+    );
+    assert_eq!(Club::SCHEMA[1], ("club_xg", DType::Float64));
+
+    let records = Player::read_records(CSV).unwrap();
+    assert_eq!(
+        records[1],
+        Player {
+            name: "Darwin Nunez",
+            stats: PlayerStats { xg: 69.69, goals: 6969 },
+            club: Club { name: "Liverpool", stats: PlayerStats { xg: 80.1, goals: 86 } },
+        }
+    );
+
+    let df = Player::to_dataframe(&records);
+    assert_eq!(df.headers()[4], "club.club_xg");
+    assert_eq!(df.col("stats.xg").unwrap(), vec![&Val::Float64(66.66), &Val::Float64(69.69)]);
+
+    let csv = df.to_csv();
+    assert_eq!(Player::read_records(&csv).unwrap(), records);
+    assert_eq!(Player::read_str(csv).unwrap().to_csv(), df.to_csv());
+}
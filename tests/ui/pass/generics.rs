@@ -0,0 +1,50 @@
+use dataframe::{macros::DataFrame, Val};
+
+#[derive(DataFrame, Debug, PartialEq)]
+struct Borrowed<'a> {
+    name: &'a str,
+    nationality: &'a str,
+    goals: u32,
+}
+
+#[derive(DataFrame, Debug, PartialEq)]
+struct Generic<T: std::str::FromStr = f64, const N: usize = 1>
+where
+    T: Copy,
+{
+    name: String,
+    xg: T,
+}
+
+#[derive(DataFrame, Debug, PartialEq)]
+pub(crate) struct Both<'a, 'b: 'a, T, U>
+where
+    T: Clone + Into<f64>,
+{
+    name: &'a str,
+    club: &'b str,
+    xg: T,
+    goals: U,
+}
+
+const CSV: &str = "name,nationality,club,xg,goals
+Lionel Messi,Argentine,Inter Miami,66.66,66
+Darwin Nunez,Uruguay,Liverpool,69.69,6969";
+
+fn main() {
+    let records = Borrowed::read_records(CSV).unwrap();
+    assert_eq!(records[1], Borrowed { name: "Darwin Nunez", nationality: "Uruguay", goals: 6969 });
+    assert!(CSV.as_bytes().as_ptr_range().contains(&records[0].name.as_ptr()));
+
+    let df = Borrowed::read_str(CSV.to_string()).unwrap();
+    assert_eq!(df.col("name").unwrap(), vec![&Val::from("Lionel Messi".to_string()), &Val::from("Darwin Nunez".to_string())]);
+
+    let records = Generic::<f64, 3>::read_records(CSV).unwrap();
+    assert_eq!(records[0].xg, 66.66);
+    let df = Generic::<f32>::read_str(CSV.to_string()).unwrap();
+    assert!(matches!(df.col("xg").unwrap()[0], Val::Float32(_)));
+
+    let records = Both::<f64, u16>::read_records(CSV).unwrap();
+    assert_eq!(records[1].club, "Liverpool");
+    assert_eq!(records[1].goals, 6969);
+}
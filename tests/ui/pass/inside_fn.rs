@@ -0,0 +1,22 @@
+fn main() {
+    #[derive(dataframe::macros::DataFrame)]
+    #[allow(dead_code)]
+    struct Player {
+        name: String,
+        goals: usize,
+    }
+
+    mod nested {
+        #[derive(dataframe::macros::DataFrame)]
+        #[allow(dead_code)]
+        pub struct Player {
+            pub name: String,
+        }
+    }
+
+    let df = Player::read_str("name,goals\nMessi,66".to_string()).unwrap();
+    assert_eq!(df.headers(), &vec!["name".to_string(), "goals".to_string()]);
+
+    let df = nested::Player::read_str("name\nMessi".to_string()).unwrap();
+    assert_eq!(df.headers(), &vec!["name".to_string()]);
+}
@@ -0,0 +1,25 @@
+// The caller's own imports must not clash with anything the derive emits,
+// and no `Error` needs to be in scope.
+use dataframe::{macros::DataFrame, DataFrame, Val};
+
+#[derive(DataFrame)]
+#[allow(dead_code)]
+struct Player {
+    name: String,
+    goals: usize,
+}
+
+#[derive(DataFrame)]
+#[allow(dead_code)]
+struct Club {
+    name: String,
+    xg: f64,
+}
+
+fn main() {
+    let players: DataFrame = Player::read_str("name,goals\nMessi,66".to_string()).unwrap();
+    let clubs: DataFrame = Club::read_str("name,xg\nInter,1.5".to_string()).unwrap();
+
+    assert_eq!(players.col("goals").unwrap(), vec![&Val::Usize(66)]);
+    assert_eq!(clubs.col("xg").unwrap(), vec![&Val::Float64(1.5)]);
+}
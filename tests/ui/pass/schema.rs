@@ -0,0 +1,46 @@
+use dataframe::{macros::DataFrame, DType, Error};
+
+#[derive(DataFrame)]
+#[allow(dead_code)]
+struct Player<'a, T> {
+    name: &'a str,
+    xg: T,
+    goals: u32,
+}
+
+#[derive(DataFrame)]
+#[dataframe(strict)]
+#[allow(dead_code)]
+struct Strict {
+    name: String,
+    goals: u32,
+}
+
+const CSV: &str = "name,nationality,xg,goals
+Lionel Messi,Argentine,66.66,66";
+
+fn main() {
+    assert_eq!(
+        Player::<f32>::SCHEMA,
+        &[("name", DType::String), ("xg", DType::Float32), ("goals", DType::Uint32)]
+    );
+    assert_eq!(Strict::SCHEMA, &[("name", DType::String), ("goals", DType::Uint32)]);
+
+    assert!(Player::<f64>::read_records(CSV).is_ok());
+    match Player::<f64>::read_str("goals,club,name\n66,Inter Miami,Messi".to_string()) {
+        Err(Error::IncompatibleStruct { missing, extra }) => {
+            assert_eq!(missing, ["xg"]);
+            assert!(extra.is_empty());
+        }
+        _ => panic!("xg is missing"),
+    }
+
+    match Strict::read_records(CSV) {
+        Err(Error::IncompatibleStruct { missing, extra }) => {
+            assert!(missing.is_empty());
+            assert_eq!(extra, ["nationality", "xg"]);
+        }
+        _ => panic!("strict should reject extra columns"),
+    }
+    assert!(Strict::read_records("goals,name\n66,Messi").is_ok());
+}